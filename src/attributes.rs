@@ -0,0 +1,338 @@
+/// An attribute ready to be emitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    /// The name as written in the html
+    pub name: String,
+    /// The value as rust
+    pub value: String,
+    /// Something for a human to check, emitted as a comment alongside the attribute
+    pub comment: Option<String>,
+}
+
+/// Html attributes whose presence alone means `true`
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+];
+
+/// Attributes typed as booleans by Dioxus which, unlike html boolean attributes, are written as `"true"` or `"false"`
+///
+/// `autofocus` is also an html boolean attribute, so is `true` when present without a value.
+const LITERAL_BOOLEAN_ATTRIBUTES: &[&str] = &["autofocus", "draggable", "spellcheck"];
+
+/// Html attributes Dioxus has identifiers for, as html writes them
+///
+/// Not exhaustive, but covers the attributes common in hand written html.
+const HTML_ATTRIBUTES: &[&str] = &[
+    "accept",
+    "accept-charset",
+    "accesskey",
+    "action",
+    "align",
+    "allow",
+    "allowfullscreen",
+    "alt",
+    "as",
+    "async",
+    "autocapitalize",
+    "autocomplete",
+    "autofocus",
+    "autoplay",
+    "background",
+    "bgcolor",
+    "border",
+    "capture",
+    "charset",
+    "checked",
+    "cite",
+    "class",
+    "color",
+    "cols",
+    "colspan",
+    "content",
+    "contenteditable",
+    "controls",
+    "coords",
+    "crossorigin",
+    "data",
+    "datetime",
+    "decoding",
+    "default",
+    "defer",
+    "dir",
+    "dirname",
+    "disabled",
+    "download",
+    "draggable",
+    "enctype",
+    "enterkeyhint",
+    "fetchpriority",
+    "for",
+    "form",
+    "formaction",
+    "formenctype",
+    "formmethod",
+    "formnovalidate",
+    "formtarget",
+    "headers",
+    "height",
+    "hidden",
+    "high",
+    "href",
+    "hreflang",
+    "http-equiv",
+    "id",
+    "inert",
+    "inputmode",
+    "integrity",
+    "is",
+    "ismap",
+    "itemprop",
+    "itemscope",
+    "itemtype",
+    "kind",
+    "label",
+    "lang",
+    "list",
+    "loading",
+    "loop",
+    "low",
+    "max",
+    "maxlength",
+    "media",
+    "method",
+    "min",
+    "minlength",
+    "multiple",
+    "muted",
+    "name",
+    "nomodule",
+    "novalidate",
+    "open",
+    "optimum",
+    "pattern",
+    "ping",
+    "placeholder",
+    "playsinline",
+    "popover",
+    "poster",
+    "preload",
+    "readonly",
+    "referrerpolicy",
+    "rel",
+    "required",
+    "reversed",
+    "role",
+    "rows",
+    "rowspan",
+    "sandbox",
+    "scope",
+    "selected",
+    "shape",
+    "size",
+    "sizes",
+    "slot",
+    "span",
+    "spellcheck",
+    "src",
+    "srcdoc",
+    "srclang",
+    "srcset",
+    "start",
+    "step",
+    "style",
+    "summary",
+    "tabindex",
+    "target",
+    "title",
+    "translate",
+    "type",
+    "usemap",
+    "value",
+    "width",
+    "wrap",
+];
+
+/// Svg attributes Dioxus has identifiers for, lowercased
+const SVG_ATTRIBUTES: &[&str] = &[
+    "clip-path",
+    "clip-rule",
+    "cx",
+    "cy",
+    "d",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "opacity",
+    "points",
+    "r",
+    "rx",
+    "ry",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-opacity",
+    "stroke-width",
+    "transform",
+    "viewbox",
+    "x",
+    "x1",
+    "x2",
+    "xmlns",
+    "y",
+    "y1",
+    "y2",
+];
+
+/// Whether the attribute is one html defines, so its case carries no meaning
+pub fn is_html_attribute(name: &str) -> bool {
+    HTML_ATTRIBUTES.contains(&name.to_lowercase().as_str())
+}
+
+/// Whether Dioxus has an identifier for the attribute, so it can be written as one
+pub fn is_known_attribute(name: &str) -> bool {
+    is_html_attribute(name) || SVG_ATTRIBUTES.contains(&name.to_lowercase().as_str())
+}
+
+/// Whether the attribute's presence alone means `true`, e.g. `disabled`
+pub fn is_boolean_attribute(name: &str) -> bool {
+    BOOLEAN_ATTRIBUTES.contains(&name.to_lowercase().as_str())
+}
+
+/// Whether the attribute's `"true"` or `"false"` value is written as a bare boolean, e.g. `draggable: true`
+pub fn is_literal_boolean_attribute(name: &str) -> bool {
+    LITERAL_BOOLEAN_ATTRIBUTES.contains(&name.to_lowercase().as_str())
+}
+
+/// Html attributes whose values are numbers, rather than text which may happen to look like one
+const NUMERIC_ATTRIBUTES: &[&str] = &[
+    "cols",
+    "colspan",
+    "height",
+    "max",
+    "maxlength",
+    "min",
+    "minlength",
+    "rows",
+    "rowspan",
+    "size",
+    "span",
+    "start",
+    "step",
+    "tabindex",
+    "width",
+];
+
+/// Whether the attribute's value is a number, so can be written as a number literal, e.g. `colspan: 2`
+pub fn is_numeric_attribute(name: &str) -> bool {
+    NUMERIC_ATTRIBUTES.contains(&name.to_lowercase().as_str())
+}
+
+/// Inline javascript event handlers, e.g. `onclick`
+pub fn is_event_handler(name: &str) -> bool {
+    name.to_lowercase()
+        .strip_prefix("on")
+        .is_some_and(|event| !event.is_empty() && event.chars().all(|x| x.is_ascii_lowercase()))
+}
+
+/// Where links point, e.g. `href`, or the older `xlink:href` in svg
+pub const fn is_link_target(name: &str) -> bool {
+    name.eq_ignore_ascii_case("href") || name.eq_ignore_ascii_case("xlink:href")
+}
+
+/// Xml namespace declarations, e.g. `xmlns` or `xmlns:xlink`
+pub fn is_namespace_declaration(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name == "xmlns" || name.starts_with("xmlns:")
+}
+
+/// Whether an attribute is matched by a denylist of names, or of prefixes ending in `*`
+pub fn is_filtered(name: &str, denylist: &[String]) -> bool {
+    let name = name.to_lowercase();
+
+    denylist.iter().any(|denied| {
+        let denied = denied.to_lowercase();
+        denied
+            .strip_suffix('*')
+            .map_or_else(|| name == denied, |prefix| name.starts_with(prefix))
+    })
+}
+
+/// The attributes of a tag, read from its source, e.g. `<video controls loop>`, in the order they're written
+///
+/// The parser drops the first character of an attribute name following one without a value, so `controls loop`
+/// would become `controls` and `oop`. Where a name is repeated, the first is kept, as in a browser.
+pub fn start_tag_attributes(source: &str) -> Vec<(&str, Option<&str>)> {
+    let is_name_end = |x: char| x.is_ascii_whitespace() || matches!(x, '=' | '>' | '/');
+    let mut rest = source
+        .strip_prefix('<')
+        .unwrap_or(source)
+        .trim_start_matches(|x| !is_name_end(x));
+    let mut attributes: Vec<(&str, Option<&str>)> = Vec::new();
+
+    loop {
+        rest = rest.trim_start_matches(|x: char| x.is_ascii_whitespace() || x == '/');
+        if rest.is_empty() || rest.starts_with('>') {
+            break;
+        }
+
+        // Malformed html like `="x"` leaves the name empty, but the value is still read past
+        let (name, after) = rest.split_at(rest.find(is_name_end).unwrap_or(rest.len()));
+        let after = after.trim_start_matches(|x: char| x.is_ascii_whitespace());
+        let (value, after) = after.strip_prefix('=').map_or((None, after), |after| {
+            let (value, after) = attribute_value(after);
+            (Some(value), after)
+        });
+        rest = after;
+
+        if !attributes
+            .iter()
+            .any(|(seen, _)| seen.eq_ignore_ascii_case(name))
+        {
+            attributes.push((name, value));
+        }
+    }
+
+    attributes
+}
+
+/// Split the value at the start of the source, quoted or not, from what follows it
+fn attribute_value(source: &str) -> (&str, &str) {
+    let source = source.trim_start_matches(|x: char| x.is_ascii_whitespace());
+
+    match source.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let quoted = &source[1..];
+            quoted.split_once(quote).unwrap_or((quoted, ""))
+        }
+        _ => source.split_at(
+            source
+                .find(|x: char| x.is_ascii_whitespace() || x == '>')
+                .unwrap_or(source.len()),
+        ),
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::errors::Result;
+use crate::{convert_with_options, ConvertOptions};
+
+/// Converts html into rsx, remembering each result so converting the same html again is free
+///
+/// Results are keyed by a hash of the html and the options used. Errors aren't remembered.
+#[derive(Debug, Default)]
+pub struct CachedConverter {
+    cache: HashMap<u64, String>,
+}
+
+impl CachedConverter {
+    /// Create a converter with nothing remembered yet
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert html into rsx, or return the result from the last time this html was converted with these options
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the html is invalid, or if the html contains invalid characters that are not unicode
+    pub fn convert(&mut self, input: &str, options: &ConvertOptions) -> Result<String> {
+        let mut hasher = DefaultHasher::new();
+        (input, options).hash(&mut hasher);
+        let key = hasher.finish();
+
+        if let Some(output) = self.cache.get(&key) {
+            return Ok(output.clone());
+        }
+
+        let output = convert_with_options(input, options)?;
+        self.cache.insert(key, output.clone());
+        Ok(output)
+    }
+
+    /// How many results are remembered
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Whether no results are remembered yet
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
@@ -0,0 +1,82 @@
+/// Html elements laid out as blocks, or not laid out as text at all, so whitespace around them doesn't render
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "base",
+    "blockquote",
+    "body",
+    "caption",
+    "col",
+    "colgroup",
+    "datalist",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "li",
+    "link",
+    "main",
+    "meta",
+    "nav",
+    "ol",
+    "optgroup",
+    "option",
+    "p",
+    "pre",
+    "script",
+    "section",
+    "source",
+    "style",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "track",
+    "ul",
+];
+
+pub fn is_block_element(name: &str) -> bool {
+    BLOCK_ELEMENTS.contains(&name.to_lowercase().as_str())
+}
+
+/// Html elements whose whitespace renders as written
+const PREFORMATTED_ELEMENTS: &[&str] = &["listing", "plaintext", "pre", "textarea", "xmp"];
+
+/// Whether whitespace renders as written in an element, because of what it is or a `white-space` style
+pub fn preserves_whitespace(name: &str, style: Option<&str>) -> bool {
+    PREFORMATTED_ELEMENTS.contains(&name.to_lowercase().as_str())
+        || style.is_some_and(|style| {
+            style
+                .split(';')
+                .filter_map(|declaration| declaration.split_once(':'))
+                .filter(|(property, _)| property.trim().eq_ignore_ascii_case("white-space"))
+                .any(|(_, value)| {
+                    let value = value.trim().to_lowercase();
+                    value.starts_with("pre") || value == "break-spaces"
+                })
+        })
+}
@@ -0,0 +1,79 @@
+//! Decoding of html character references in text, as rsx shows strings as written
+
+use std::borrow::Cow;
+
+/// Named character references common in hand written html
+///
+/// Html defines a couple of thousand; references not listed here are left as written.
+const NAMED: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("apos", '\''),
+    ("bull", '\u{2022}'),
+    ("copy", '\u{a9}'),
+    ("deg", '\u{b0}'),
+    ("euro", '\u{20ac}'),
+    ("gt", '>'),
+    ("hellip", '\u{2026}'),
+    ("laquo", '\u{ab}'),
+    ("ldquo", '\u{201c}'),
+    ("lsquo", '\u{2018}'),
+    ("lt", '<'),
+    ("mdash", '\u{2014}'),
+    ("middot", '\u{b7}'),
+    ("nbsp", '\u{a0}'),
+    ("ndash", '\u{2013}'),
+    ("pound", '\u{a3}'),
+    ("quot", '"'),
+    ("raquo", '\u{bb}'),
+    ("rdquo", '\u{201d}'),
+    ("reg", '\u{ae}'),
+    ("rsquo", '\u{2019}'),
+    ("times", '\u{d7}'),
+    ("trade", '\u{2122}'),
+];
+
+/// The character a reference like `nbsp`, `#160` or `#xa0` stands for, without its `&` and `;`
+fn character(reference: &str) -> Option<char> {
+    reference.strip_prefix('#').map_or_else(
+        || {
+            NAMED
+                .iter()
+                .find(|(name, _)| *name == reference)
+                .map(|(_, chara)| *chara)
+        },
+        |number| {
+            let code = number
+                .strip_prefix(['x', 'X'])
+                .map_or_else(|| number.parse(), |hex| u32::from_str_radix(hex, 16));
+            code.ok().and_then(char::from_u32)
+        },
+    )
+}
+
+/// Replace character references with the characters they stand for, e.g. `&amp;` with `&`
+pub fn decode(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest
+            .find(';')
+            .and_then(|end| character(&rest[1..end]).map(|chara| (chara, end)));
+        if let Some((chara, end)) = decoded {
+            out.push(chara);
+            rest = &rest[end + 1..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+
+    Cow::Owned(out)
+}
@@ -1,10 +1,76 @@
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
 use thiserror::Error;
 
+/// Results returned by the converter
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Errors that may occur while converting html into rsx
 #[derive(Error, Debug, Diagnostic)]
 pub enum Error {
+    /// The html could not be parsed
     #[error("failed to parse html")]
     HtmlParseError(#[from] tl::errors::ParseError),
+    /// A tag was found without a name, so there is nothing to emit for it
+    #[error("found a tag with no name")]
+    #[diagnostic(help("remove the tag, or give it a name"))]
+    EmptyTagName {
+        /// The html being converted
+        #[source_code]
+        src: String,
+        /// Where the tag is in the original html
+        #[label("this tag has no name")]
+        span: SourceSpan,
+    },
+    /// The rsx could not be written out
+    #[error("failed to write rsx")]
+    #[diagnostic(help("check the writer being converted into can be written to"))]
+    WriteError(#[from] std::io::Error),
+    /// The blocking task converting the html didn't finish
+    #[cfg(feature = "tokio")]
+    #[error("conversion task failed")]
+    #[diagnostic(help("the runtime may be shutting down"))]
+    TaskFailed(#[from] tokio::task::JoinError),
+    /// The output grew larger than the configured limit
+    #[error("output exceeded the limit of {limit} bytes")]
+    #[diagnostic(help("increase max_output_bytes, or convert a smaller piece of html"))]
+    OutputTooLarge {
+        /// The configured limit in bytes
+        limit: usize,
+    },
+    /// The html was nested deeper than the configured limit
+    #[error("nesting exceeded the limit of {limit} levels")]
+    #[diagnostic(help("increase max_depth, or flatten the html"))]
+    MaxDepthExceeded {
+        /// The configured limit in levels
+        limit: usize,
+    },
+    /// The name given for a generated component isn't a rust identifier
+    #[error("{name:?} can't be used as the name of a component")]
+    #[diagnostic(help(
+        "use an identifier like `App`, without spaces or punctuation, that isn't a keyword"
+    ))]
+    InvalidComponentName {
+        /// The name that was given
+        name: String,
+    },
+    /// The name given for a generated constant isn't a rust identifier
+    #[error("{name:?} can't be used as the name of a constant")]
+    #[diagnostic(help(
+        "use an identifier like `RSX`, without spaces or punctuation, that isn't a keyword"
+    ))]
+    InvalidConstName {
+        /// The name that was given
+        name: String,
+    },
+    /// The rsx produced couldn't be parsed by dioxus
+    #[cfg(feature = "validate")]
+    #[error("converted rsx is invalid: {reason}")]
+    #[diagnostic(help("this is a bug in the converter, or html it can't convert yet"))]
+    InvalidRsx {
+        /// The rsx that was produced
+        #[source_code]
+        rsx: String,
+        /// Why dioxus couldn't parse it
+        reason: String,
+    },
 }
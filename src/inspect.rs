@@ -0,0 +1,81 @@
+use tl::Node;
+
+use crate::errors::Result;
+use crate::{rust, tag_name};
+
+/// A construct in the html that can't be faithfully represented in rsx
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsupported {
+    /// What was found
+    pub kind: UnsupportedKind,
+    /// Roughly where it was found, as a byte offset into the html
+    pub offset: usize,
+}
+
+/// The kinds of construct that can't be faithfully represented in rsx
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsupportedKind {
+    /// A `<script>` element, whose javascript won't run in rsx
+    Script,
+    /// A `<![CDATA[...]]>` section
+    CData,
+    /// An internet explorer conditional comment, e.g. `<!--[if IE]>`
+    ConditionalComment,
+    /// A tag whose name isn't a valid rust identifier, e.g. `<x-button>`
+    InvalidTagName,
+}
+
+/// List the constructs in html that can't be faithfully converted into rsx
+///
+/// Useful for estimating how much manual work a conversion will need before running it.
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid
+pub fn inspect(input: &str) -> Result<Vec<Unsupported>> {
+    let trimmed = input.trim();
+    let trimmed_prefix = input.len() - input.trim_start().len();
+    let dom = tl::parse(trimmed, tl::ParserOptions::default())?;
+    let parser = dom.parser();
+    // Text and comments don't carry their position, but do borrow from the html, which gives it away
+    let offset_of =
+        |bytes: &[u8]| trimmed_prefix + (bytes.as_ptr() as usize - trimmed.as_ptr() as usize);
+
+    Ok(dom
+        .nodes()
+        .iter()
+        .filter_map(|node| {
+            let kind = match node {
+                Node::Tag(tag) => {
+                    // Resolved as the converter does by default, which drops the slash and namespace
+                    let name = tag_name(tag);
+                    let name = name.rsplit(':').next().unwrap_or(name);
+
+                    if name.eq_ignore_ascii_case("script") {
+                        UnsupportedKind::Script
+                    } else if rust::is_identifier(name) {
+                        return None;
+                    } else {
+                        UnsupportedKind::InvalidTagName
+                    }
+                }
+                Node::Raw(text) if text.as_bytes().starts_with(b"[CDATA[") => {
+                    UnsupportedKind::CData
+                }
+                Node::Comment(comment) if comment.as_bytes().starts_with(b"<!--[if") => {
+                    UnsupportedKind::ConditionalComment
+                }
+                Node::Raw(_) | Node::Comment(_) => return None,
+            };
+
+            let offset = match node {
+                Node::Tag(tag) => trimmed_prefix + tag.boundaries(parser).0,
+                // The parser swallows the `<!` opening a CDATA section
+                Node::Raw(text) => offset_of(text.as_bytes()).saturating_sub(2),
+                Node::Comment(comment) => offset_of(comment.as_bytes()),
+            };
+
+            Some(Unsupported { kind, offset })
+        })
+        .collect())
+}
@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+use crate::{attributes, escape_string, rust, AttributeCase, ConvertOptions};
+
+/// How an attribute name is written as a key in rsx
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AttrKey {
+    /// An identifier, e.g. `class`, `view_box`, or the raw identifier `r#type`
+    Ident(String),
+    /// A string, for names rsx has no identifier for, e.g. `"data-id"` or `"xlink:href"`
+    StringKey(String),
+}
+
+impl Display for AttrKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ident(ident) => f.write_str(ident),
+            Self::StringKey(key) => write!(f, "\"{}\"", escape_string(key, false)),
+        }
+    }
+}
+
+/// The rsx key for an html attribute name, as used when converting
+///
+/// Component props name fields on the component's props struct, so only have hyphens replaced. For elements, custom
+/// `data-` and `aria-` attributes are strings, as are names which can't be identifiers. Otherwise names which html
+/// treats case-insensitively are lowercased, camel case humps become snake case, hyphens become underscores, and
+/// keywords become raw identifiers.
+#[must_use]
+pub fn html_attr_to_rsx_key(name: &str, is_component: bool) -> AttrKey {
+    rsx_key(name, is_component, &ConvertOptions::default())
+}
+
+/// The rsx key for an html attribute name, following the options for how element attribute names are written
+pub fn rsx_key(name: &str, is_component: bool, options: &ConvertOptions) -> AttrKey {
+    if is_component {
+        return rust::identifier(&name.replace('-', "_"))
+            .map_or_else(|| AttrKey::StringKey(name.to_string()), AttrKey::Ident);
+    }
+
+    let normalised = normalise_attribute_case(name);
+    let unknown = options.unknown_attributes_as_strings
+        && !attributes::is_event_handler(name)
+        && !attributes::is_known_attribute(name);
+    if is_custom(name) || unknown {
+        return AttrKey::StringKey(normalised.into_owned());
+    }
+
+    let key = match options.attribute_case {
+        AttributeCase::SnakeCase => snake_case(&normalised).replace('-', "_"),
+        AttributeCase::Preserve => normalised.replace('-', "_"),
+        AttributeCase::CamelCase => camel_case(&normalised),
+    };
+    rust::identifier(&key).map_or_else(|| AttrKey::StringKey(name.to_string()), AttrKey::Ident)
+}
+
+/// Custom attributes, which rsx only accepts as strings
+fn is_custom(name: &str) -> bool {
+    ["data-", "aria-"].iter().any(|prefix| {
+        name.get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    })
+}
+
+/// Lowercase names where the case carries no meaning, keeping camel case names like svg's `viewBox`
+fn normalise_attribute_case(name: &str) -> Cow<'_, str> {
+    let shouted = !name.chars().any(char::is_lowercase);
+
+    if shouted
+        || is_custom(name)
+        || attributes::is_event_handler(name)
+        || attributes::is_html_attribute(name)
+    {
+        Cow::Owned(name.to_lowercase())
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// Split camel case humps with underscores, e.g. `viewBox` to `view_box`
+fn snake_case(name: &str) -> String {
+    name.chars()
+        .enumerate()
+        .map(|(idx, chara)| {
+            if idx == 0 {
+                chara.to_lowercase().to_string()
+            } else {
+                chara.to_string()
+            }
+        })
+        .map(|x| {
+            if x == x.to_lowercase() {
+                x
+            } else {
+                ["_".to_string(), x.to_lowercase()].concat()
+            }
+        })
+        .collect()
+}
+
+/// Join hyphenated or underscored words into camel case humps, e.g. `stroke-width` to `strokeWidth`
+fn camel_case(name: &str) -> String {
+    let mut words = name.split(['-', '_']).filter(|word| !word.is_empty());
+    let first = words.next().unwrap_or_default().to_string();
+
+    words.fold(first, |mut out, word| {
+        let mut chars = word.chars();
+        out.extend(chars.next().map(|chara| chara.to_ascii_uppercase()));
+        out.push_str(chars.as_str());
+        out
+    })
+}
@@ -0,0 +1,39 @@
+//! Emission for the Leptos `view!` macro, which keeps the html shape of tags
+
+use crate::attributes::{is_event_handler, Attribute};
+
+/// Push `<name key=value>`, or `<name key=value/>` when there are no children to follow
+pub fn push_open_tag(out: &mut String, name: &str, attributes: &[Attribute], has_children: bool) {
+    out.push('<');
+    out.push_str(name);
+
+    for attribute in attributes {
+        out.push(' ');
+        // Tags are written on one line, so comments about attributes go in blocks before them
+        if let Some(comment) = &attribute.comment {
+            out.push_str("/* ");
+            out.push_str(&comment.replace("*/", "* /").replace("/*", "/ *"));
+            out.push_str(" */ ");
+        }
+        if is_event_handler(&attribute.name) {
+            out.push_str("on:");
+            out.push_str(&attribute.name[2..].to_lowercase());
+        } else {
+            out.push_str(&attribute.name);
+        }
+        out.push('=');
+        out.push_str(&attribute.value);
+    }
+
+    if has_children {
+        out.push_str(">\n");
+    } else {
+        out.push_str("/>\n");
+    }
+}
+
+pub fn push_close_tag(out: &mut String, name: &str) {
+    out.push_str("</");
+    out.push_str(name);
+    out.push_str(">\n");
+}
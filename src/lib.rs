@@ -23,238 +23,3989 @@
 )]
 
 use std::borrow::Cow;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+use std::ops::Range;
 
-use tl::Node;
+use tl::{HTMLTag, Node};
 
-use errors::Result;
+use attributes::Attribute;
+pub use cache::CachedConverter;
+pub use errors::{Error, Result};
+pub use inspect::{inspect, Unsupported, UnsupportedKind};
+pub use keys::{html_attr_to_rsx_key, AttrKey};
+pub use options::{
+    AttributeCase, AttributePlacement, BlankLines, ConvertOptions, DioxusVersion, EmptyBraces,
+    EmptyTagNames, JsHandlerStyle, Preset, TagNamespaces, Target, TextBraces, TextChildStyle,
+};
+pub use output::{ConvertResult, SourceMapEntry};
 
+mod attributes;
+mod cache;
+mod elements;
+mod entities;
 mod errors;
+mod inspect;
+mod keys;
+mod leptos;
+mod options;
+mod output;
+mod rust;
 
+#[derive(Debug)]
 enum Fragment<'a> {
     TlNode(&'a Node<'a>),
+    /// Attributes placed after the children of a tag, and whether the tag is a component
+    Attributes(Vec<Attribute>, bool),
     ClosingBrace,
+    ClosingTag(&'a str),
+    /// The end of the macro call wrapping the output, e.g. `})`
+    ClosingMacro(&'static str),
 }
 
-fn escape_string(input: &str) -> String {
-    input
+fn escape_string(input: &str, ascii_only: bool) -> String {
+    let escaped = input
         .replace('\\', "\\\\")
         .replace('\"', "\\\"")
         .replace('\n', "\\n")
         .replace('\r', "\\r")
-        .replace('\t', "\\t")
+        .replace('\t', "\\t");
+
+    // Whitespace like non-breaking spaces is invisible in an editor, so is escaped even when it could be written as is
+    escaped
+        .chars()
+        .map(|chara| {
+            if chara.is_ascii() || !(ascii_only || chara.is_whitespace()) {
+                chara.to_string()
+            } else {
+                format!("\\u{{{:x}}}", u32::from(chara))
+            }
+        })
+        .collect()
+}
+
+/// Non-ASCII characters written as `\u{...}` escapes when only ASCII is to be emitted, e.g. for comments
+fn ascii_escaped(input: &str, ascii_only: bool) -> Cow<'_, str> {
+    if ascii_only && !input.is_ascii() {
+        Cow::Owned(
+            input
+                .chars()
+                .map(|chara| {
+                    if chara.is_ascii() {
+                        chara.to_string()
+                    } else {
+                        format!("\\u{{{:x}}}", u32::from(chara))
+                    }
+                })
+                .collect(),
+        )
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+fn to_rust_string(input: &str, ascii_only: bool) -> String {
+    format!("\"{}\"", escape_string(input, ascii_only))
+}
+
+/// A raw string literal, when it reads better than escaping, e.g. for windows paths or regexes
+///
+/// Raw strings can't hold escapes, so values which need any beyond quotes and backslashes are left to
+/// [`to_rust_string`].
+fn to_raw_rust_string(input: &str, ascii_only: bool) -> Option<String> {
+    let escapes = input.chars().filter(|x| matches!(x, '"' | '\\')).count();
+    let unrepresentable = input
+        .chars()
+        .any(|x| x.is_control() || (ascii_only && !x.is_ascii()));
+    if escapes < 2 || unrepresentable {
+        return None;
+    }
+
+    // Enough hashes that no quote in the value can close the string early
+    let hashes = (0..=input.len())
+        .map(|count| "#".repeat(count))
+        .find(|hashes| !input.contains(&format!("\"{hashes}")))
+        .unwrap_or_default();
+
+    Some(format!("r{hashes}\"{input}\"{hashes}"))
+}
+
+/// The name of a tag as written
+///
+/// The parser keeps the slash of a self-closing tag without attributes, like `<br/>`, as part of the name.
+fn tag_name<'a>(tag: &'a HTMLTag<'a>) -> &'a str {
+    tag.name()
+        .try_as_utf8_str()
+        .unwrap_or_default()
+        .trim_end_matches('/')
 }
 
-fn to_rust_string(input: &str) -> String {
-    format!("\"{}\"", escape_string(input))
+/// Components are written in `PascalCase`, unlike html elements
+///
+/// Html is case-insensitive, so a shouted name like `DIV` is an element rather than a component.
+fn is_component(tag_name: &str) -> bool {
+    tag_name.starts_with(char::is_uppercase) && tag_name.contains(char::is_lowercase)
 }
 
-const INDENTATION: usize = 4;
+/// An attribute as an rsx field, e.g. `class: "x"`, or `class` when the value is a variable of the same name
+fn rsx_field(attribute: &Attribute, component: bool, options: &ConvertOptions) -> String {
+    let key = keys::rsx_key(&attribute.name, component, options);
+
+    match key {
+        // Only expressions are written bare, so a value matching the key is a variable of that name
+        AttrKey::Ident(ident) if options.shorthand_attributes && ident == attribute.value => ident,
+        AttrKey::StringKey(key) => format!(
+            "{}: {}",
+            to_rust_string(&key, options.ascii_only),
+            attribute.value
+        ),
+        AttrKey::Ident(_) => format!("{key}: {}", attribute.value),
+    }
+}
 
 /// Convert html into rsx
 ///
+/// Passing a `&str` borrows the input rather than allocating a copy of it; see also [`convert_str`].
+///
 /// # Errors
 ///
 /// Will return an error if the html is invalid, or if the html contains invalid characters that are not unicode
 pub fn convert<'a>(input: impl Into<Cow<'a, str>>) -> Result<String> {
+    convert_with_options(input, &ConvertOptions::default())
+}
+
+/// Convert a html string slice into rsx
+///
+/// A non-generic shorthand for [`convert`] which shares its implementation. The input is only borrowed, so it is never
+/// copied before parsing.
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, or if the html contains invalid characters that are not unicode
+pub fn convert_str(input: &str) -> Result<String> {
+    convert(input)
+}
+
+/// Convert several html documents into rsx, with a result for each, so one failing doesn't stop the rest
+#[must_use]
+pub fn convert_many(inputs: &[&str]) -> Vec<Result<String>> {
+    convert_many_with_options(inputs, &ConvertOptions::default())
+}
+
+/// Convert several html documents into rsx with the same options, with a result for each, so one failing doesn't stop
+/// the rest
+#[must_use]
+pub fn convert_many_with_options(inputs: &[&str], options: &ConvertOptions) -> Vec<Result<String>> {
+    inputs
+        .iter()
+        .map(|input| convert_with_options(*input, options))
+        .collect()
+}
+
+/// Convert html into rsx, checking that dioxus can parse the rsx produced
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, or if the rsx produced isn't something dioxus can parse
+#[cfg(feature = "validate")]
+pub fn convert_validated(input: &str) -> Result<String> {
+    use std::str::FromStr;
+
+    let rsx = convert(input)?;
+    let parsed = proc_macro2::TokenStream::from_str(&rsx)
+        .map_err(|err| err.to_string())
+        .and_then(|tokens| {
+            syn::parse2::<dioxus_rsx::CallBody>(tokens).map_err(|err| err.to_string())
+        });
+
+    match parsed {
+        Ok(_) => Ok(rsx),
+        Err(reason) => Err(Error::InvalidRsx { rsx, reason }),
+    }
+}
+
+/// Convert the attributes of an html element into rsx fields, one per line, e.g. `class: "x",`
+///
+/// Attributes are given as names alongside their values, or `None` for attributes without a value. They go through the
+/// same filtering, ordering, and naming as when converting a whole document.
+#[must_use]
+pub fn convert_attributes(attributes: &[(&str, Option<&str>)], options: &ConvertOptions) -> String {
+    let attributes = attributes
+        .iter()
+        .map(|(name, value)| (Cow::Borrowed(*name), value.map(Cow::Borrowed)))
+        .collect();
+
+    let mut out = String::new();
+    for attribute in attribute_values(attributes, false, options) {
+        if let Some(comment) = &attribute.comment {
+            out.push_str("// ");
+            out.push_str(comment);
+            out.push('\n');
+        }
+
+        out.push_str(&rsx_field(&attribute, false, options));
+        out.push_str(",\n");
+    }
+
+    out
+}
+
+/// Convert html into a Dioxus component without props, rendering the rsx
+///
+/// The signature follows [`ConvertOptions::dioxus_version`], e.g. `fn App() -> Element` for 0.5.
+///
+/// # Errors
+///
+/// Will return an error if `name` isn't a rust identifier, if the html is invalid, or if the html contains invalid
+/// characters that are not unicode
+pub fn convert_to_component(input: &str, name: &str, options: &ConvertOptions) -> Result<String> {
+    if !rust::is_item_name(name) {
+        return Err(Error::InvalidComponentName {
+            name: name.to_string(),
+        });
+    }
+
+    let signature = match options.dioxus_version {
+        DioxusVersion::V0_4 => "(cx: Scope)",
+        DioxusVersion::V0_5 => "()",
+    };
+    let options = ConvertOptions {
+        target: Target::Dioxus,
+        wrap_in_macro: true,
+        ..options.clone()
+    };
+    let indent = " ".repeat(options.child_indent);
+
+    let mut out = format!("fn {name}{signature} -> Element {{\n");
+    for line in convert_with_options(input, &options)?.lines() {
+        if !line.is_empty() {
+            out.push_str(&indent);
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out.push_str("}\n");
+
+    Ok(out)
+}
+
+/// Convert html into rsx on a single line, for inlining into other code, e.g. `div { class: "x", "hi" }`
+///
+/// Comments are written as block comments, so they don't swallow the rest of the line.
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, or if the html contains invalid characters that are not unicode
+pub fn convert_inline(input: &str) -> Result<String> {
+    let options = ConvertOptions {
+        attribute_indent: 0,
+        child_indent: 0,
+        ..ConvertOptions::default()
+    };
+    let rsx = convert_with_options(input, &options)?;
+
+    let lines = rsx.lines().collect::<Vec<_>>();
+    let mut out = String::with_capacity(rsx.len());
+    for (idx, line) in lines.iter().enumerate() {
+        if idx > 0 {
+            out.push(' ');
+        }
+
+        if let Some(comment) = line.strip_prefix("//") {
+            out.push_str("/*");
+            out.push_str(&comment.replace("*/", "* /").replace("/*", "/ *"));
+            out.push_str(" */");
+        } else if lines.get(idx + 1) == Some(&"}") {
+            // Nothing follows the last field, so it needs no separating comma
+            out.push_str(line.strip_suffix(',').unwrap_or(line));
+        } else {
+            out.push_str(line);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Convert html into rsx, written as a string constant for build scripts to generate, e.g.
+/// `pub const RSX: &str = "div {}\n";`
+///
+/// # Errors
+///
+/// Will return an error if `const_name` isn't a rust identifier, if the html is invalid, or if the html contains invalid
+/// characters that are not unicode
+pub fn convert_to_const(input: &str, const_name: &str) -> Result<String> {
+    if !rust::is_item_name(const_name) {
+        return Err(Error::InvalidConstName {
+            name: const_name.to_string(),
+        });
+    }
+
+    let rsx = convert(input)?;
+
+    Ok(format!(
+        "pub const {const_name}: &str = {};\n",
+        to_rust_string(&rsx, false)
+    ))
+}
+
+/// Convert html into rsx, split into lines for comparing against golden output
+///
+/// The output is deterministic: attributes are emitted in a stable order, indentation is consistent, and no line ends
+/// in whitespace. So comparing lines gives a readable diff when a conversion changes.
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, or if the html contains invalid characters that are not unicode
+pub fn convert_lines(input: &str, options: &ConvertOptions) -> Result<Vec<String>> {
+    convert_with_options(input, options).map(|rsx| rsx.lines().map(String::from).collect())
+}
+
+/// Convert html into rsx, returning the options used and the attributes renamed alongside it
+///
+/// Accepts either [`ConvertOptions`] or a [`Preset`], so tools can show which options a preset expanded into.
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, or if the html contains invalid characters that are not unicode
+pub fn convert_to_result(input: &str, options: impl Into<ConvertOptions>) -> Result<ConvertResult> {
+    let options = options.into();
+    let mut output = String::new();
+    let Report {
+        renamed_attributes, ..
+    } = convert_in_pieces(input, &options, |piece| {
+        output.push_str(piece);
+        Ok(())
+    })?;
+
+    Ok(ConvertResult {
+        output,
+        options,
+        renamed_attributes,
+    })
+}
+
+/// Convert html into rsx, alongside where each element in the html ended up in the rsx
+///
+/// Entries are in the order the elements are written, so parents come before their children.
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, or if the html contains invalid characters that are not unicode
+pub fn convert_with_source_map(input: &str) -> Result<(String, Vec<SourceMapEntry>)> {
+    let mut output = String::new();
+    let Report { element_spans, .. } =
+        convert_in_pieces(input, &ConvertOptions::default(), |piece| {
+            output.push_str(piece);
+            Ok(())
+        })?;
+
+    let line = |offset: usize| output[..offset].matches('\n').count();
+    let source_map = element_spans
+        .into_iter()
+        .map(|(source, rsx)| SourceMapEntry {
+            source,
+            output_lines: line(rsx.start)..line(rsx.end),
+        })
+        .collect();
+
+    Ok((output, source_map))
+}
+
+/// Convert html into rsx on tokio's blocking thread pool, so conversion doesn't hold up other tasks
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, if the html contains invalid characters that are not unicode, or if
+/// the blocking task fails to finish
+#[cfg(feature = "tokio")]
+pub async fn convert_async(input: impl Into<String>) -> Result<String> {
     let input = input.into();
-    let dom = tl::parse(input.trim(), tl::ParserOptions::default())?;
-    let parser = dom.parser();
 
-    let mut work_stack = dom
+    tokio::task::spawn_blocking(move || convert(input.as_str())).await?
+}
+
+/// Convert html into rsx, customising the output with [`ConvertOptions`]
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, or if the html contains invalid characters that are not unicode
+pub fn convert_with_options<'a>(
+    input: impl Into<Cow<'a, str>>,
+    options: &ConvertOptions,
+) -> Result<String> {
+    let mut out = String::new();
+    convert_in_pieces(&input.into(), options, |piece| {
+        out.push_str(piece);
+        Ok(())
+    })?;
+
+    Ok(out)
+}
+
+/// Convert html into rsx, writing the rsx for each top level element as soon as it's complete
+///
+/// The html is still parsed in one go, but only the rsx for one top level element is held at a time, so converting
+/// many independent elements doesn't build up the whole output in memory. When wrapping the output in a macro or a
+/// fragment, the wrapper's opening is written first, and its closing once everything within it is.
+///
+/// # Errors
+///
+/// Will return an error if the html is invalid, if the html contains invalid characters that are not unicode, or if
+/// the writer fails
+pub fn convert_to_writer(
+    input: &str,
+    options: &ConvertOptions,
+    writer: &mut impl Write,
+) -> Result<()> {
+    convert_in_pieces(input, options, |piece| {
+        Ok(writer.write_all(piece.as_bytes())?)
+    })
+    .map(|_| ())
+}
+
+/// What was noticed while converting, besides the rsx itself
+struct Report {
+    /// Attribute names which were written differently in the rsx, alongside what they were written as
+    renamed_attributes: Vec<(String, String)>,
+    /// The span of each element in the html, alongside the span of bytes of the rsx it became
+    element_spans: Vec<(Range<usize>, Range<usize>)>,
+}
+
+/// Convert html into rsx, handing the rsx over each time a top level node is complete
+fn convert_in_pieces(
+    input: &str,
+    options: &ConvertOptions,
+    mut emit: impl FnMut(&str) -> Result<()>,
+) -> Result<Report> {
+    // Files saved with a byte order mark would otherwise have it read as text before the first element
+    let html = input.strip_prefix('\u{feff}').unwrap_or(input);
+    // Offsets from the parser are relative to the trimmed html, so spans need shifting back by what was trimmed
+    let trimmed_prefix = input.len() - html.trim_start().len();
+    let dom = tl::parse(html.trim(), tl::ParserOptions::default())?;
+    let parser = dom.parser();
+    let roots = dom
         .children()
         .iter()
         .filter_map(|x| x.get(parser))
-        .map(Fragment::TlNode)
-        .collect::<VecDeque<_>>();
+        .collect::<Vec<_>>();
 
-    let mut out = String::new();
-    let mut indentation_level = 0;
+    let roots = significant_nodes(&roots);
+    let wrapped = options.wrap_roots && roots.len() > 1;
+
+    let mut converter = Converter::new(options, parser, roots, trimmed_prefix);
+
+    let macro_close = options.wrap_in_macro.then(|| converter.push_macro());
+    if wrapped {
+        converter.push_fragment();
+    }
+    // Queued after the fragment's closing brace, so the macro is closed last
+    if let Some(close) = macro_close {
+        converter
+            .work_stack
+            .push_back(Fragment::ClosingMacro(close));
+    }
+    converter.root_level = converter.indentation_level;
+    // The wrappers' openings are complete in themselves, so needn't wait for what they wrap
+    if !converter.out.is_empty() {
+        converter.hand_over(&mut emit)?;
+    }
 
-    while let Some(work) = work_stack.pop_front() {
+    while let Some(work) = converter.work_stack.pop_front() {
         match work {
             Fragment::TlNode(Node::Tag(tag)) => {
-                out.push_str(indentation_spaces(indentation_level).as_ref());
-                out.push_str(tag.name().try_as_utf8_str().unwrap_or_default());
-                out.push_str(" {");
-
-                let mut attr = tag.attributes().iter().collect::<Vec<_>>();
-                attr.sort();
-                for (key, value) in &attr {
-                    let value = value
-                        .as_deref()
-                        .map_or_else(|| String::from("true"), to_rust_string);
-
-                    let key = key
-                        .chars()
-                        .enumerate()
-                        .map(|(idx, chara)| {
-                            if idx == 0 {
-                                chara.to_lowercase().to_string()
-                            } else {
-                                chara.to_string()
-                            }
-                        })
-                        .map(|x| {
-                            if x == x.to_lowercase() {
-                                x
-                            } else {
-                                ["_".to_string(), x.to_lowercase()].concat()
-                            }
-                        })
-                        .collect::<String>()
-                        .replace('-', "_");
-
-                    out.push('\n');
-                    out.push_str(indentation_spaces(indentation_level + 1).as_str());
-                    out.push_str(key.as_ref());
-                    out.push_str(": ");
-                    out.push_str(value.as_ref());
-                    out.push(',');
+                let unnamed = tag.name().as_bytes().is_empty();
+                match options.empty_tag_names {
+                    _ if converter.is_dropped(tag) => {}
+                    _ if !unnamed => {
+                        converter.check_depth()?;
+                        converter.push_tag(tag);
+                    }
+                    // There's no element to emit, but its children may still be worth keeping
+                    EmptyTagNames::Unwrap => {
+                        let keep_whitespace = converter.preformatted_level.is_some();
+                        converter.push_children(converter.children(tag, keep_whitespace));
+                    }
+                    EmptyTagNames::Error => {
+                        let (start, end) = tag.boundaries(parser);
+                        return Err(Error::EmptyTagName {
+                            src: input.to_string(),
+                            span: (trimmed_prefix + start, end - start + 1).into(),
+                        });
+                    }
                 }
-                if tag.children().start().is_none() && !tag.attributes().is_empty() {
-                    out.push('\n');
-                    out.push_str(indentation_spaces(indentation_level).as_ref());
+            }
+            Fragment::TlNode(Node::Raw(text)) => {
+                converter.push_text(&entities::decode(
+                    text.try_as_utf8_str().unwrap_or_default(),
+                ));
+            }
+            Fragment::TlNode(Node::Comment(comment)) => {
+                converter.push_comment(comment.try_as_utf8_str().unwrap_or_default());
+            }
+            Fragment::Attributes(attributes, component) => {
+                // Still at the level of the children, rather than the tag the attributes belong to
+                converter.indentation_level -= 1;
+                converter.push_attributes(&attributes, component);
+                converter.indentation_level += 1;
+            }
+            Fragment::ClosingBrace => {
+                converter.close_level();
+                converter.push_indentation();
+                converter.out.push_str("}\n");
+                converter.finish_element();
+            }
+            Fragment::ClosingTag(name) => {
+                converter.close_level();
+                converter.push_indentation();
+                leptos::push_close_tag(&mut converter.out, name);
+                converter.finish_element();
+            }
+            Fragment::ClosingMacro(close) => {
+                converter.indentation_level -= 1;
+                converter.push_indentation();
+                converter.out.push_str(close);
+                converter.out.push('\n');
+            }
+        }
+
+        if let Some(limit) = options.max_output_bytes {
+            if converter.emitted + converter.out.len() > limit {
+                return Err(Error::OutputTooLarge { limit });
+            }
+        }
+
+        // Back at the top level, or closing the wrappers around it, so everything so far is complete
+        if converter.indentation_level <= converter.root_level {
+            converter.hand_over(&mut emit)?;
+        }
+    }
+
+    Ok(Report {
+        renamed_attributes: converter.renamed_attributes,
+        element_spans: converter.element_spans,
+    })
+}
+
+/// State for a single conversion, walking the html depth first
+struct Converter<'a> {
+    options: &'a ConvertOptions,
+    parser: &'a tl::Parser<'a>,
+    work_stack: VecDeque<Fragment<'a>>,
+    out: String,
+    indentation_level: usize,
+    /// How many bytes of rsx have already been handed over
+    emitted: usize,
+    /// Where the html the parser was given starts in the input, as offsets from the parser are relative to it
+    source_offset: usize,
+    /// The span of each element in the html, alongside the span of bytes of the rsx it became
+    element_spans: Vec<(Range<usize>, Range<usize>)>,
+    /// The indentation level of each element whose children are being converted, alongside its index in the spans
+    open_elements: Vec<(usize, usize)>,
+    /// The indentation level of the top level nodes of the html, inside any macro call or fragment wrapping them
+    root_level: usize,
+    /// The indentation level of the element just finished, if nothing has been written since
+    closed_level: Option<usize>,
+    /// The indentation level of the children of the outermost `svg` being converted, if within one
+    svg_level: Option<usize>,
+    /// The indentation level of the children of the outermost element preserving whitespace, if within one
+    preformatted_level: Option<usize>,
+    /// Attribute names written differently in the rsx, alongside what they were written as
+    renamed_attributes: Vec<(String, String)>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(
+        options: &'a ConvertOptions,
+        parser: &'a tl::Parser<'a>,
+        roots: Vec<&'a Node<'a>>,
+        source_offset: usize,
+    ) -> Self {
+        Self {
+            options,
+            parser,
+            work_stack: roots.into_iter().map(Fragment::TlNode).collect(),
+            out: String::new(),
+            indentation_level: 0,
+            emitted: 0,
+            source_offset,
+            element_spans: Vec::new(),
+            open_elements: Vec::new(),
+            root_level: 0,
+            closed_level: None,
+            svg_level: None,
+            preformatted_level: None,
+            renamed_attributes: Vec::new(),
+        }
+    }
+
+    fn indentation(&self) -> String {
+        " ".repeat(self.indentation_level * self.options.child_indent)
+    }
+
+    fn push_indentation(&mut self) {
+        let indentation = self.indentation();
+        self.out.push_str(&indentation);
+    }
+
+    /// Step out of the children of a tag
+    fn close_level(&mut self) {
+        self.indentation_level -= 1;
+        self.closed_level = Some(self.indentation_level);
+        if self.svg_level > Some(self.indentation_level) {
+            self.svg_level = None;
+        }
+        if self.preformatted_level > Some(self.indentation_level) {
+            self.preformatted_level = None;
+        }
+    }
+
+    /// How many bytes of rsx have been written, including those already handed over
+    const fn written(&self) -> usize {
+        self.emitted + self.out.len()
+    }
+
+    /// Fail if the element about to be written is nested deeper than allowed
+    ///
+    /// Only elements count, so text and comments within the deepest element allowed are still written.
+    const fn check_depth(&self) -> Result<()> {
+        match self.options.max_depth {
+            // Top level elements are written at the root level, and each enclosing element indents them further
+            Some(limit) if self.indentation_level - self.root_level > limit => {
+                Err(Error::MaxDepthExceeded { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Hand over the rsx written so far, keeping count of how much has been
+    fn hand_over(&mut self, emit: &mut impl FnMut(&str) -> Result<()>) -> Result<()> {
+        emit(&self.out)?;
+        self.emitted += self.out.len();
+        self.out.clear();
+        Ok(())
+    }
+
+    /// Record where the element whose children were just closed ends in the rsx
+    fn finish_element(&mut self) {
+        if let Some(&(level, idx)) = self.open_elements.last() {
+            if level == self.indentation_level {
+                self.open_elements.pop();
+                let written = self.written();
+                if let Some((_, output)) = self.element_spans.get_mut(idx) {
+                    output.end = written;
                 }
+            }
+        }
+    }
+
+    /// Separate an element from the sibling element before it, if configured to
+    fn push_sibling_separator(&mut self) {
+        let separated = match self.options.blank_line_between_siblings {
+            BlankLines::Never => false,
+            BlankLines::TopLevel => self.indentation_level == self.root_level,
+            BlankLines::Everywhere => true,
+        };
+
+        if separated && self.closed_level == Some(self.indentation_level) {
+            self.out.push('\n');
+        }
+    }
+
+    /// Open the macro call wrapping the output, returning what closes it
+    fn push_macro(&mut self) -> &'static str {
+        let (open, close) = match (self.options.target, self.options.dioxus_version) {
+            (Target::Dioxus, DioxusVersion::V0_4) => ("cx.render(rsx! {", "})"),
+            (Target::Dioxus, DioxusVersion::V0_5) => ("rsx! {", "}"),
+            (Target::Leptos, _) => ("view! {", "}"),
+        };
+
+        self.out.push_str(open);
+        self.out.push('\n');
+        self.indentation_level += 1;
+        close
+    }
+
+    /// Open a fragment around everything still to be converted
+    fn push_fragment(&mut self) {
+        self.push_indentation();
+        match self.options.target {
+            Target::Dioxus => {
+                self.out.push_str("Fragment {\n");
+                self.work_stack.push_back(Fragment::ClosingBrace);
+            }
+            Target::Leptos => {
+                self.out.push_str("<>\n");
+                self.work_stack.push_back(Fragment::ClosingTag(""));
+            }
+        }
+        self.indentation_level += 1;
+    }
+
+    /// Whether the tag is one of the elements to drop, along with everything in it
+    fn is_dropped(&self, tag: &HTMLTag<'_>) -> bool {
+        let drop_elements = &self.options.drop_elements;
+        !drop_elements.is_empty() && drop_elements.contains(&tag_name(tag).to_lowercase())
+    }
+
+    /// The name of a tag after any renaming
+    fn renamed_tag_name(&self, tag: &'a HTMLTag<'a>) -> &'a str {
+        let options = self.options;
+        let name = tag_name(tag);
+        options.tag_renames.get(name).map_or_else(
+            || match options.tag_namespaces {
+                TagNamespaces::Strip => name.rsplit(':').next().unwrap_or(name),
+                TagNamespaces::Keep => name,
+            },
+            String::as_str,
+        )
+    }
+
+    /// The name of an element as written in Dioxus rsx
+    fn rsx_element(&self, name: &str, component: bool) -> String {
+        // Elements sharing a name with a keyword, like svg's `use`, are written as raw identifiers
+        match &self.options.component_module_prefix {
+            Some(prefix) if component => format!("{prefix}::{name}"),
+            _ => {
+                // A shouted element like `DIV` is written as rsx's lowercase `div`
+                let name = if name.contains(char::is_lowercase) {
+                    Cow::Borrowed(name)
+                } else {
+                    Cow::Owned(name.to_lowercase())
+                };
+                rust::identifier(&name).unwrap_or_else(|| name.into_owned())
+            }
+        }
+    }
 
-                if tag.children().start().is_none() {
-                    out.push_str("}\n");
+    fn push_tag(&mut self, tag: &'a HTMLTag<'a>) {
+        let options = self.options;
+        let name = self.renamed_tag_name(tag);
+        let component = is_component(name);
+        let attributes = self.tag_attributes(tag, name, component);
+        // Read from the source, as tl loses values following a valueless attribute
+        let source = tag.raw().try_as_utf8_str().unwrap_or_default();
+        let style = attributes::start_tag_attributes(source)
+            .into_iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("style"))
+            .and_then(|(_, value)| value);
+        let preformatted = elements::preserves_whitespace(name, style);
+        // Whitespace is preserved for all descendants, e.g. the `code` within a `pre`
+        let keep_whitespace = preformatted || self.preformatted_level.is_some();
+        let children = self.children(tag, keep_whitespace);
+        let mut has_children = !children.is_empty();
+
+        self.push_sibling_separator();
+        let (start, end) = tag.boundaries(self.parser);
+        let source = self.source_offset + start..self.source_offset + end + 1;
+        let written = self.written();
+        self.element_spans.push((source, written..written));
+        self.push_indentation();
+        match options.target {
+            Target::Dioxus => {
+                let element = self.rsx_element(name, component);
+                self.record_renames(&attributes, component);
+                let collapsed = if has_children {
+                    self.folded_tag(&children, name, &element, &attributes)
+                } else if options.collapse_short_tags {
+                    self.collapsed_tag(&element, &attributes, component)
+                } else {
+                    None
+                };
+
+                if let Some(line) = collapsed {
+                    // Everything in a folded tag is already written
+                    has_children = false;
+                    self.out.push_str(&line);
+                    self.out.push('\n');
                 } else {
-                    out.push('\n');
-                    work_stack.push_front(Fragment::ClosingBrace);
-
-                    for child in tag
-                        .children()
-                        .top()
-                        .iter()
-                        .filter_map(|x| x.get(parser))
-                        .collect::<Vec<_>>()
-                        .iter()
-                        .rev()
-                    {
-                        work_stack.push_front(Fragment::TlNode(child));
+                    self.out.push_str(&element);
+                    self.out.push_str(" {");
+                    if has_children || !attributes.is_empty() {
+                        self.out.push('\n');
+                    }
+
+                    if has_children {
+                        self.work_stack.push_front(Fragment::ClosingBrace);
+                        match options.attribute_placement {
+                            AttributePlacement::Before => {
+                                self.push_attributes(&attributes, component);
+                            }
+                            AttributePlacement::After => self
+                                .work_stack
+                                .push_front(Fragment::Attributes(attributes, component)),
+                        }
+                    } else if !attributes.is_empty() {
+                        self.push_attributes(&attributes, component);
+                        self.push_indentation();
+                        self.out.push_str("}\n");
+                    } else if options.empty_brace_style == EmptyBraces::Spaced {
+                        self.out.push_str(" }\n");
+                    } else {
+                        self.out.push_str("}\n");
                     }
-                    indentation_level += 1;
                 }
             }
-            Fragment::TlNode(Node::Raw(text)) => {
-                out.push_str(" ".repeat(indentation_level * INDENTATION).as_ref());
-                out.push_str(to_rust_string(text.try_as_utf8_str().unwrap_or_default()).as_ref());
-                out.push('\n');
+            Target::Leptos => {
+                leptos::push_open_tag(&mut self.out, name, &attributes, has_children);
+
+                if has_children {
+                    self.work_stack.push_front(Fragment::ClosingTag(name));
+                }
+            }
+        }
+
+        if has_children {
+            if self.svg_level.is_none() && name.eq_ignore_ascii_case("svg") {
+                self.svg_level = Some(self.indentation_level + 1);
+            }
+            if preformatted && self.preformatted_level.is_none() {
+                self.preformatted_level = Some(self.indentation_level + 1);
+            }
+            self.push_children(children);
+            self.open_elements
+                .push((self.indentation_level, self.element_spans.len() - 1));
+            self.indentation_level += 1;
+        } else {
+            self.closed_level = Some(self.indentation_level);
+            let written = self.written();
+            if let Some((_, output)) = self.element_spans.last_mut() {
+                output.end = written;
+            }
+        }
+    }
+
+    /// The attributes to emit for a tag, already renamed to `name`
+    fn tag_attributes(&self, tag: &HTMLTag<'_>, name: &str, component: bool) -> Vec<Attribute> {
+        let source = tag.raw().try_as_utf8_str().unwrap_or_default();
+        let mut attributes = attributes::start_tag_attributes(source)
+            .into_iter()
+            .map(|(key, value)| (Cow::Borrowed(key), value.map(Cow::Borrowed)))
+            .collect::<Vec<_>>();
+        if self.options.drop_svg_xmlns && name.eq_ignore_ascii_case("svg") {
+            attributes.retain(|(key, _)| !attributes::is_namespace_declaration(key));
+        }
+        let mut attributes = attribute_values(attributes, component, self.options);
+
+        // Dioxus only has an html `a`, so a link within svg would be created in the wrong namespace
+        if self.svg_level.is_some() && name.eq_ignore_ascii_case("a") {
+            for attribute in &mut attributes {
+                if attribute.comment.is_none() && attributes::is_link_target(&attribute.name) {
+                    attribute.comment = Some(String::from(
+                        "dioxus creates `a` as an html element, so this link won't render within svg",
+                    ));
+                }
+            }
+        }
+
+        attributes
+    }
+
+    /// The children of a tag which will be converted, without dropped elements or insignificant whitespace
+    fn children(&self, tag: &HTMLTag<'_>, keep_whitespace: bool) -> Vec<&'a Node<'a>> {
+        let mut children = Vec::new();
+        self.extend_children(tag, &mut children);
+
+        if keep_whitespace {
+            children
+        } else {
+            significant_nodes(&children)
+        }
+    }
+
+    /// Add the children of a tag which are converted, with the children of unwrapped unnamed tags in their place
+    fn extend_children(&self, tag: &HTMLTag<'_>, children: &mut Vec<&'a Node<'a>>) {
+        for child in tag
+            .children()
+            .top()
+            .iter()
+            .filter_map(|x| x.get(self.parser))
+        {
+            match child.as_tag() {
+                Some(child) if self.is_dropped(child) => {}
+                Some(child)
+                    if child.name().as_bytes().is_empty()
+                        && self.options.empty_tag_names == EmptyTagNames::Unwrap =>
+                {
+                    self.extend_children(child, children);
+                }
+                _ => children.push(child),
+            }
+        }
+    }
+
+    /// Queue nodes to be converted next
+    fn push_children(&mut self, children: Vec<&'a Node<'a>>) {
+        for child in children.into_iter().rev() {
+            self.work_stack.push_front(Fragment::TlNode(child));
+        }
+    }
+
+    /// A tag with only text or an inline chain in it laid out on one line, if configured to and it fits within the
+    /// maximum width
+    fn folded_tag(
+        &self,
+        children: &[&'a Node<'a>],
+        name: &str,
+        element: &str,
+        attributes: &[Attribute],
+    ) -> Option<String> {
+        let line = self.folded(children, name, element, attributes)?;
+        (self.indentation().len() + line.len() <= self.options.max_width).then_some(line)
+    }
+
+    /// A tag and everything in it on one line, e.g. `strong { em { "text" } }`, if it has no attributes and only text
+    /// or, for inline elements, another such inline element in it
+    fn folded(
+        &self,
+        children: &[&'a Node<'a>],
+        name: &str,
+        element: &str,
+        attributes: &[Attribute],
+    ) -> Option<String> {
+        let options = self.options;
+        let [child] = children else {
+            return None;
+        };
+        let is_inline = |name: &str| !is_component(name) && !elements::is_block_element(name);
+        let chain = options.fold_inline_chains && is_inline(name);
+        if !attributes.is_empty() {
+            return None;
+        }
+
+        let inner = match child {
+            Node::Raw(text) if options.fold_text_leaves || chain => {
+                let text = text.try_as_utf8_str()?;
+                let (literal, flagged) = self.text_literal(&entities::decode(text));
+                // Text needing a comment can't share its line
+                (!flagged && !text.trim().is_empty()).then_some(literal)?
+            }
+            Node::Tag(child) if chain => {
+                let child_name = self.renamed_tag_name(child);
+                if !is_inline(child_name) {
+                    return None;
+                }
+                let attributes = self.tag_attributes(child, child_name, false);
+                let element = self.rsx_element(child_name, false);
+                let keep_whitespace = self.preformatted_level.is_some();
+                let children = self.children(child, keep_whitespace);
+                self.folded(&children, child_name, &element, &attributes)?
+            }
+            _ => return None,
+        };
+
+        Some(format!("{element} {{ {inner} }}"))
+    }
+
+    /// A childless tag laid out on one line, if it fits within the maximum width
+    fn collapsed_tag(
+        &self,
+        name: &str,
+        attributes: &[Attribute],
+        component: bool,
+    ) -> Option<String> {
+        let line = format!(
+            "{name} {{ {} }}",
+            attributes
+                .iter()
+                .map(|attribute| rsx_field(attribute, component, self.options))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let fits = self.indentation().len() + line.len() <= self.options.max_width;
+        let commented = attributes.iter().any(|x| x.comment.is_some());
+
+        (!attributes.is_empty() && fits && !commented).then_some(line)
+    }
+
+    /// Remember attributes whose names are written differently in the rsx, once each
+    fn record_renames(&mut self, attributes: &[Attribute], component: bool) {
+        for attribute in attributes {
+            let rename = (
+                attribute.name.clone(),
+                keys::rsx_key(&attribute.name, component, self.options).to_string(),
+            );
+            if rename.0 != rename.1 && !self.renamed_attributes.contains(&rename) {
+                self.renamed_attributes.push(rename);
+            }
+        }
+    }
+
+    /// Push a line for each attribute, indented relative to their tag
+    fn push_attributes(&mut self, attributes: &[Attribute], component: bool) {
+        let indentation = self.indentation() + &" ".repeat(self.options.attribute_indent);
+
+        for attribute in attributes {
+            if let Some(comment) = &attribute.comment {
+                self.out.push_str(&indentation);
+                self.out.push_str("// ");
+                self.out.push_str(comment);
+                self.out.push('\n');
+            }
+
+            self.out.push_str(&indentation);
+            self.out
+                .push_str(&rsx_field(attribute, component, self.options));
+            self.out.push_str(",\n");
+        }
+    }
+
+    /// Text as rsx, and whether it needs flagging for braces rsx will treat as interpolation
+    fn text_literal(&self, text: &str) -> (String, bool) {
+        let options = self.options;
+        let braced =
+            options.target == Target::Dioxus && options.text_child_style == TextChildStyle::Braced;
+        // Bare strings in rsx are format strings, so braces would be read as interpolation
+        let interpolated = options.target == Target::Dioxus && !braced && text.contains(['{', '}']);
+
+        let text = if interpolated && options.text_braces == TextBraces::Escape {
+            Cow::Owned(text.replace('{', "{{").replace('}', "}}"))
+        } else {
+            Cow::Borrowed(text)
+        };
+
+        let literal = to_rust_string(&text, options.ascii_only);
+        let literal = if braced {
+            format!("{{{literal}}}")
+        } else {
+            literal
+        };
+        (
+            literal,
+            interpolated && options.text_braces == TextBraces::Flag,
+        )
+    }
+
+    fn push_text(&mut self, text: &str) {
+        self.closed_level = None;
+        // Outside preformatted text, a run of whitespace renders as a single space
+        let collapsed = self.preformatted_level.is_none()
+            && text
+                .trim_matches(|x: char| x.is_ascii_whitespace())
+                .is_empty();
+        let (literal, flagged) = self.text_literal(if collapsed { " " } else { text });
+
+        if flagged {
+            self.push_indentation();
+            self.out
+                .push_str("// contains braces, which rsx will treat as interpolation\n");
+        }
+
+        self.push_indentation();
+        self.out.push_str(&literal);
+        self.out.push('\n');
+    }
+
+    fn push_comment(&mut self, comment: &str) {
+        self.closed_level = None;
+        // Only the outermost delimiters, as the body may itself contain something like them
+        let comment = comment.strip_prefix("<!--").unwrap_or(comment);
+        let comment = comment.strip_suffix("-->").unwrap_or(comment).trim();
+
+        // Every line is commented, as a line comment ends at the newline
+        let lines = if comment.is_empty() {
+            vec![""]
+        } else {
+            comment.lines().map(str::trim).collect()
+        };
+        for line in lines {
+            self.push_indentation();
+            self.out.push_str("//");
+            // Only separate a non-empty line, so lines never end in a space
+            if !line.is_empty() {
+                self.out.push(' ');
+                self.out
+                    .push_str(&ascii_escaped(line, self.options.ascii_only));
+            }
+            self.out.push('\n');
+        }
+    }
+}
+
+/// Drop whitespace-only text which a browser wouldn't render
+///
+/// Whitespace next to a block element is insignificant, as is whitespace at the start or end of an element's children.
+/// Whitespace between inline elements on the same line separates them, so is kept, to be written as a single space.
+/// Whitespace spanning lines is taken to be indentation of the html, as in jsx, so is dropped. Only ascii whitespace
+/// collapses, so text of non-breaking spaces, written as is or as `&nbsp;`, is always kept.
+fn significant_nodes<'a>(nodes: &[&'a Node<'a>]) -> Vec<&'a Node<'a>> {
+    let is_block = |sibling: Option<&&Node<'_>>| {
+        sibling.is_none_or(|sibling| {
+            sibling
+                .as_tag()
+                .is_some_and(|tag| elements::is_block_element(tag_name(tag)))
+        })
+    };
+
+    nodes
+        .iter()
+        .enumerate()
+        .filter(|(idx, node)| {
+            let whitespace = node.as_raw().and_then(|text| {
+                let text = text.try_as_utf8_str().unwrap_or_default();
+                text.trim_matches(|x: char| x.is_ascii_whitespace())
+                    .is_empty()
+                    .then_some(text)
+            });
+
+            whitespace.is_none_or(|whitespace| {
+                !(whitespace.contains('\n')
+                    || is_block(idx.checked_sub(1).and_then(|prev| nodes.get(prev)))
+                    || is_block(nodes.get(idx + 1)))
+            })
+        })
+        .map(|(_, node)| *node)
+        .collect()
+}
+
+/// The attributes to emit for a tag, as their original names alongside their values as rust
+fn attribute_values(
+    mut attr: Vec<(Cow<'_, str>, Option<Cow<'_, str>>)>,
+    component: bool,
+    options: &ConvertOptions,
+) -> Vec<Attribute> {
+    // Malformed html can leave attributes without a name, which have no key to be emitted under
+    attr.retain(|(key, _)| {
+        !key.trim().is_empty() && !attributes::is_filtered(key, &options.attribute_filter)
+    });
+    attr.sort();
+    // Event handlers read best after the attributes describing the element
+    attr.sort_by_key(|(key, _)| !component && attributes::is_event_handler(key));
+
+    let prefix = options.expression_prefix.as_deref();
+
+    attr.into_iter()
+        .map(|(key, value)| {
+            let value = value.as_deref().map(|x| {
+                if options.trim_attribute_values {
+                    x.trim()
+                } else {
+                    x
+                }
+            });
+            let template = value.and_then(template_expression);
+            let comment = template
+                .filter(|_| !options.template_expressions)
+                .map(|_| String::from("template expression, this may need to be a binding"));
+
+            // A prefix with nothing after it has no expression to bind, so is left as a string
+            let bound = value
+                .and_then(|x| prefix.and_then(|prefix| x.strip_prefix(prefix)))
+                .map(str::trim)
+                .filter(|x| !x.is_empty());
+
+            let value = match (value, template) {
+                (Some(x), _) if bound.is_some() => bound.unwrap_or(x).to_string(),
+                (_, Some(expression)) if options.template_expressions => {
+                    format!("\"{{{expression}}}\"")
+                }
+                (x, _) if !component && attributes::is_event_handler(&key) => {
+                    match (options.js_handler_style, x) {
+                        // Block comments nest in rust, so openings need breaking up as well as closings
+                        (JsHandlerStyle::Commented, Some(x)) => format!(
+                            "move |_| {{ /* {} */ }}",
+                            ascii_escaped(x, options.ascii_only)
+                                .replace("*/", "* /")
+                                .replace("/*", "/ *")
+                        ),
+                        // A handler without a value has no javascript to keep
+                        (JsHandlerStyle::Empty, _) | (_, None) => String::from("move |_| {}"),
+                    }
+                }
+                (None, _) => String::from("true"),
+                (Some(""), _) if attributes::is_boolean_attribute(&key) => String::from("true"),
+                (Some(x @ ("true" | "false")), _)
+                    if attributes::is_literal_boolean_attribute(&key) =>
+                {
+                    x.to_string()
+                }
+                (Some(x), _)
+                    if options.unquote_numbers
+                        && attributes::is_numeric_attribute(&key)
+                        && rust::is_number(x) =>
+                {
+                    x.to_string()
+                }
+                (Some(x), _)
+                    if (options.sort_class_tokens || options.dedupe_class_tokens)
+                        && key.eq_ignore_ascii_case("class") =>
+                {
+                    let mut tokens = x.split_whitespace().collect::<Vec<_>>();
+                    if options.dedupe_class_tokens {
+                        let mut seen = HashSet::new();
+                        tokens.retain(|token| seen.insert(*token));
+                    }
+                    if options.sort_class_tokens {
+                        tokens.sort_unstable();
+                    }
+                    to_rust_string(&tokens.join(" "), options.ascii_only)
+                }
+                (Some(x), _) => options
+                    .raw_strings
+                    .then(|| to_raw_rust_string(x, options.ascii_only))
+                    .flatten()
+                    .unwrap_or_else(|| to_rust_string(x, options.ascii_only)),
+            };
+
+            Attribute {
+                name: key.into_owned(),
+                value,
+                comment,
+            }
+        })
+        .collect()
+}
+
+/// The expression inside a `{{ expression }}` placeholder, as used by many templating engines
+fn template_expression(value: &str) -> Option<&str> {
+    value
+        .trim()
+        .strip_prefix("{{")
+        .and_then(|x| x.strip_suffix("}}"))
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn empty_input() {
+        let actual = convert("");
+        assert_eq!(actual.expect("Failed to convert html"), "");
+    }
+
+    #[test]
+    fn whitespace_only_input() {
+        let actual = convert("   \n  ");
+        assert_eq!(actual.expect("Failed to convert html"), "");
+    }
+
+    #[test]
+    fn comment_only_input() {
+        let input = indoc! {"
+
+            <!-- only a comment -->
+        "};
+
+        let actual = convert(input);
+        assert_eq!(
+            actual.expect("Failed to convert html"),
+            "// only a comment\n"
+        );
+    }
+
+    #[test]
+    fn byte_order_mark_is_stripped() {
+        let input = "\u{feff}<div></div>";
+        let expected = indoc! {"
+        div {}
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn empty_div() {
+        let input = indoc! {"
+        <div></div>
+        "};
+
+        let expected = indoc! {"
+        div {}
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn empty_braces_can_be_spaced() {
+        let input = indoc! {r#"
+        <div><br><img src="a.png"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            br { }
+            img {
+                src: "a.png",
+            }
+        }
+        "#};
+        let options = ConvertOptions {
+            empty_brace_style: EmptyBraces::Spaced,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn empty_braces_are_tight_by_default() {
+        let input = indoc! {"
+        <div><br></div>
+        "};
+
+        let expected = indoc! {"
+        div {
+            br {}
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn void_element_as_the_only_child() {
+        let input = indoc! {r#"
+        <div>
+            <div><img src="a.png"></div>
+        </div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            div {
+                img {
+                    src: "a.png",
+                }
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn convert_str_matches_convert() {
+        let input = "<div id=\"x\">text</div>";
+
+        let actual = convert_str(input);
+        assert_eq!(
+            actual.expect("Failed to convert html"),
+            convert(input).expect("Failed to convert html")
+        );
+    }
+
+    #[test]
+    fn empty_span() {
+        let input = indoc! {"
+        <span></span>
+        "};
+
+        let expected = indoc! {"
+        span {}
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn self_closing_div() {
+        let input = indoc! {"
+        <div />
+        "};
+
+        let expected = indoc! {"
+        div {}
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn self_closing_span_with_attributes() {
+        let input = indoc! {r#"
+        <span class="x"/>
+        "#};
+
+        let expected = indoc! {r#"
+        span {
+            class: "x",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn comments_become_rust_comments() {
+        let input = indoc! {"
+        <div><!-- nothing in here --></div><!-- nothing out here -->
+        "};
+
+        let expected = indoc! {"
+        div {
+            // nothing in here
+        }
+        // nothing out here
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn comments_share_indentation_with_the_following_element() {
+        let input = indoc! {"
+        <div><!-- note --><span></span></div>
+        "};
+
+        let expected = indoc! {"
+        div {
+            // note
+            span {}
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn comment_delimiters_are_only_stripped_once() {
+        let input = indoc! {"
+        <div><!--<!-- a -- -> b --->--> c --></div>
+        "};
+
+        let expected = indoc! {"
+        div {
+            // <!-- a -- -> b --->
+            \" c -->\"
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn quotes_and_backslashes_in_comments_are_not_escaped() {
+        let input = indoc! {r#"
+        <!-- use "quotes" and C:\paths\ here -->
+        <p>x</p>
+        "#};
+
+        let expected = indoc! {r#"
+        // use "quotes" and C:\paths\ here
+        p {
+            "x"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn every_line_of_a_comment_is_commented() {
+        let input = indoc! {"
+        <div><!-- a -- b
+            c -> d
+
+            e --></div>
+        "};
+
+        let expected = indoc! {"
+        div {
+            // a -- b
+            // c -> d
+            //
+            // e
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn shouted_elements_are_not_components() {
+        let input = indoc! {r#"
+        <DIV CLASS="x"><Card Label="y"/></DIV>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            class: "x",
+            Card {
+                Label: "y",
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn consecutive_comments_are_kept_separate() {
+        let input = indoc! {"
+        <!-- a --><!-- b -->
+        "};
+
+        let expected = indoc! {"
+        // a
+        // b
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn attributes_and_children_are_laid_out_consistently() {
+        let input = indoc! {r#"
+        <div></div>
+        <div id="a"></div>
+        <div>x</div>
+        <div id="b">y</div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {}
+        div {
+            id: "a",
+        }
+        div {
+            "x"
+        }
+        div {
+            id: "b",
+            "y"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn attributes_can_be_placed_after_children() {
+        let input = indoc! {r#"
+        <div id="a"><p class="b">x</p><br></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            p {
+                "x"
+                class: "b",
+            }
+            br {}
+            id: "a",
+        }
+        "#};
+        let options = ConvertOptions {
+            attribute_placement: AttributePlacement::After,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn div_with_attributes() {
+        let input = indoc! {"
+        <div class=\"example\"></div>
+        "};
+
+        let expected = indoc! {"
+        div {
+            class: \"example\",
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn div_with_multiple_attributes() {
+        let input = indoc! {"
+        <div class=\"example\" id=\"id\"></div>
+        "};
+
+        let expected = indoc! {"
+        div {
+            class: \"example\",
+            id: \"id\",
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn div_with_irregular_attributes() {
+        let input = indoc! {r#"
+        <div stroke-linecap="round" ></div>
+        "#};
+
+        let expected = indoc! {"
+        div {
+            stroke_linecap: \"round\",
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn attribute_values_with_newlines_stay_on_one_line() {
+        let input = indoc! {r#"
+        <div title="first
+        second"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            title: "first\nsecond",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn div_with_inner_text() {
+        let input = indoc! {"
+        <div>Some text</div>
+        "};
+
+        let expected = indoc! {"
+        div {
+            \"Some text\"
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn non_snake_capitalised_attributes_are_converted_to_snake() {
+        let input = indoc! {r#"
+        <div SomeAttribute="door"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            some_attribute: "door",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn shouted_attribute_keys_are_lowercased() {
+        assert_eq!(
+            html_attr_to_rsx_key("CLASS", false),
+            AttrKey::Ident(String::from("class"))
+        );
+    }
+
+    #[test]
+    fn event_handler_keys_are_lowercased() {
+        assert_eq!(
+            html_attr_to_rsx_key("onClick", false),
+            AttrKey::Ident(String::from("onclick"))
+        );
+    }
+
+    #[test]
+    fn data_attribute_containing_json_is_escaped() {
+        let input = indoc! {r#"
+        <div data-json='{"a":1}'></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            "data-json": "{\"a\":1}",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn data_attribute_keys_are_lowercased_strings() {
+        assert_eq!(
+            html_attr_to_rsx_key("data-ID", false),
+            AttrKey::StringKey(String::from("data-id"))
+        );
+    }
+
+    #[test]
+    fn aria_attribute_keys_are_strings() {
+        assert_eq!(
+            html_attr_to_rsx_key("aria-label", false),
+            AttrKey::StringKey(String::from("aria-label"))
+        );
+    }
+
+    #[test]
+    fn snake_case_attribute_keys_are_unchanged() {
+        for name in ["data_foo", "some_thing", "stroke_width"] {
+            let key = html_attr_to_rsx_key(name, false);
+            assert_eq!(key, AttrKey::Ident(name.to_string()));
+            assert_eq!(html_attr_to_rsx_key(&key.to_string(), false), key);
+        }
+    }
+
+    #[test]
+    fn camel_case_attribute_keys_become_snake_case() {
+        assert_eq!(
+            html_attr_to_rsx_key("viewBox", false),
+            AttrKey::Ident(String::from("view_box"))
+        );
+    }
+
+    #[test]
+    fn hyphenated_attribute_keys_become_snake_case() {
+        assert_eq!(
+            html_attr_to_rsx_key("accept-charset", false),
+            AttrKey::Ident(String::from("accept_charset"))
+        );
+    }
+
+    #[test]
+    fn keyword_attribute_keys_are_raw_identifiers() {
+        assert_eq!(
+            html_attr_to_rsx_key("type", false),
+            AttrKey::Ident(String::from("r#type"))
+        );
+    }
+
+    #[test]
+    fn non_raw_keyword_attribute_keys_are_strings() {
+        assert_eq!(
+            html_attr_to_rsx_key("self", false),
+            AttrKey::StringKey(String::from("self"))
+        );
+    }
+
+    #[test]
+    fn namespaced_attribute_keys_are_strings() {
+        assert_eq!(
+            html_attr_to_rsx_key("xlink:href", false),
+            AttrKey::StringKey(String::from("xlink:href"))
+        );
+    }
+
+    #[test]
+    fn component_prop_keys_keep_their_case() {
+        assert_eq!(
+            html_attr_to_rsx_key("onClick", true),
+            AttrKey::Ident(String::from("onClick"))
+        );
+        assert_eq!(
+            html_attr_to_rsx_key("data-ID", true),
+            AttrKey::Ident(String::from("data_ID"))
+        );
+    }
+
+    #[test]
+    fn attribute_keys_are_displayed_as_rsx() {
+        assert_eq!(AttrKey::Ident(String::from("r#type")).to_string(), "r#type");
+        assert_eq!(
+            AttrKey::StringKey(String::from("data-id")).to_string(),
+            "\"data-id\""
+        );
+    }
+
+    #[test]
+    fn attributes_can_be_converted_alone() {
+        let attributes = [
+            ("onClick", Some("go()")),
+            ("CLASS", Some("x")),
+            ("data-ID", Some("1")),
+            ("viewBox", Some("0 0 1 1")),
+            ("type", Some("button")),
+            ("xlink:href", Some("#a")),
+            ("hidden", None),
+        ];
+
+        let expected = indoc! {r##"
+        class: "x",
+        "data-id": "1",
+        hidden: true,
+        r#type: "button",
+        view_box: "0 0 1 1",
+        "xlink:href": "#a",
+        onclick: move |_| { /* go() */ },
+        "##};
+        let actual = convert_attributes(&attributes, &ConvertOptions::default());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn keyword_attribute_names_are_raw_identifiers_or_strings() {
+        let input = indoc! {r#"
+        <div match="x" move="y" self="z"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            r#match: "x",
+            r#move: "y",
+            "self": "z",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn attributes_without_names_are_dropped() {
+        let input = indoc! {r#"
+        <div ="x" id="a"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            id: "a",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn enumerated_global_attributes_are_strings() {
+        let input = indoc! {r#"
+        <p translate="no" dir="rtl" lang="ar">x</p>
+        "#};
+
+        let expected = indoc! {r#"
+        p {
+            dir: "rtl",
+            lang: "ar",
+            translate: "no",
+            "x"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn solo_attributes_are_marked_as_true() {
+        let input = indoc! {r"
+        <input disabled />
+        "};
+
+        let expected = indoc! {r"
+        input {
+            disabled: true,
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn non_ascii_is_escaped_when_ascii_only() {
+        let input = indoc! {"
+        <p title=\"café\">café 😀</p>
+        "};
+
+        let expected = indoc! {r#"
+        p {
+            title: "caf\u{e9}",
+            "caf\u{e9} \u{1f600}"
+        }
+        "#};
+        let options = ConvertOptions {
+            ascii_only: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn non_ascii_comments_and_keys_are_escaped_when_ascii_only() {
+        let input = indoc! {"
+        <button data-café=\"x\" onclick=\"go('😀')\"><!-- café --></button>
+        "};
+
+        let expected = indoc! {r#"
+        button {
+            "data-caf\u{e9}": "x",
+            onclick: move |_| { /* go('\u{1f600}') */ },
+            // caf\u{e9}
+        }
+        "#};
+        let options = ConvertOptions {
+            ascii_only: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn values_with_quotes_and_backslashes_can_be_raw_strings() {
+        let input = indoc! {r#"
+        <div data-path="C:\Users\me" data-quote='say "hi"' title="plain"></div>
+        "#};
+
+        let expected = indoc! {"
+        div {
+            \"data-path\": r\"C:\\Users\\me\",
+            \"data-quote\": r#\"say \"hi\"\"#,
+            title: \"plain\",
+        }
+        "};
+        let options = ConvertOptions {
+            raw_strings: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn emoji_sequences_and_combining_characters_are_kept_intact() {
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let accented = "e\u{301}te\u{301}";
+        let input = format!("<span>{family} {accented}</span>");
+
+        let expected = format!("span {{\n    \"{family} {accented}\"\n}}\n");
+        let actual = convert(input.as_str());
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn srcset_is_kept_as_a_single_string() {
+        let input = indoc! {r#"
+        <img srcset="img-320.png 320w, img-640.png 640w" />
+        "#};
+
+        let expected = indoc! {r#"
+        img {
+            srcset: "img-320.png 320w, img-640.png 640w",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn error_spans_account_for_trimmed_whitespace() {
+        let input = "\n\n  <div><></div>";
+
+        let options = ConvertOptions {
+            empty_tag_names: EmptyTagNames::Error,
+            ..ConvertOptions::default()
+        };
+        let actual = match convert_with_options(input, &options) {
+            Err(Error::EmptyTagName { span, .. }) => Some((span.offset(), span.len())),
+            _ => None,
+        };
+        assert_eq!(
+            actual,
+            Some((9, 2)),
+            "Span should cover the unnamed tag in the untrimmed html"
+        );
+    }
+
+    #[test]
+    fn tags_without_a_name_are_unwrapped() {
+        let input = "<div><>a</></div>";
+
+        let expected = indoc! {r#"
+        div {
+            "a"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn attribute_names_can_keep_their_case() {
+        let input = indoc! {r#"
+        <div dataValue="z" CLASS="x" stroke-width="2"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            class: "x",
+            dataValue: "z",
+            stroke_width: "2",
+        }
+        "#};
+        let options = ConvertOptions {
+            attribute_case: AttributeCase::Preserve,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn unknown_attributes_can_be_written_as_strings() {
+        let input = indoc! {r#"
+        <button foobar="x" class="y" onclick="z" data-id="1" aria-label="w"></button>
+        "#};
+
+        let expected = indoc! {r#"
+        button {
+            "aria-label": "w",
+            class: "y",
+            "data-id": "1",
+            "foobar": "x",
+            onclick: move |_| { /* z */ },
+        }
+        "#};
+        let options = ConvertOptions {
+            unknown_attributes_as_strings: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn attribute_names_can_be_camel_case() {
+        let input = indoc! {r#"
+        <svg viewBox="0 0 1 1" stroke-width="2" data-id="x"></svg>
+        "#};
+
+        let expected = indoc! {r#"
+        svg {
+            "data-id": "x",
+            strokeWidth: "2",
+            viewBox: "0 0 1 1",
+        }
+        "#};
+        let options = ConvertOptions {
+            attribute_case: AttributeCase::CamelCase,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn nested_component_props_keep_their_case() {
+        let input = indoc! {r#"
+        <Card Title="t"><Button Label="x" onClick="y"/><div dataValue="z"></div></Card>
+        "#};
+
+        let expected = indoc! {r#"
+        Card {
+            Title: "t",
+            Button {
+                Label: "x",
+                onClick: "y",
+            }
+            div {
+                data_value: "z",
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn output_larger_than_the_limit_is_an_error() {
+        let input = indoc! {r#"
+        <div class="a-long-class-name"><p>Some long text</p></div>
+        "#};
+
+        let options = ConvertOptions {
+            max_output_bytes: Some(16),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert!(
+            matches!(actual, Err(Error::OutputTooLarge { limit: 16 })),
+            "Expected the output limit to be hit"
+        );
+    }
+
+    #[test]
+    fn nesting_deeper_than_the_limit_is_an_error() {
+        let input = "<div>".repeat(64);
+
+        let options = ConvertOptions {
+            max_depth: Some(8),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(&input, &options);
+        assert!(
+            matches!(actual, Err(Error::MaxDepthExceeded { limit: 8 })),
+            "Expected the depth limit to be hit"
+        );
+    }
+
+    #[test]
+    fn nesting_within_the_limit_is_converted() {
+        let options = ConvertOptions {
+            max_depth: Some(2),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options("<div><p>a</p></div>", &options);
+        assert_eq!(
+            actual.expect("Failed to convert html"),
+            "div {\n    p {\n        \"a\"\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn nesting_limit_only_counts_elements() {
+        let options = ConvertOptions {
+            max_depth: Some(1),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options("<div><p>a<!-- b --></p></div>", &options);
+        assert_eq!(
+            actual.expect("Failed to convert html"),
+            "div {\n    p {\n        \"a\"\n        // b\n    }\n}\n"
+        );
+
+        let actual = convert_with_options("<div><p><b>a</b></p></div>", &options);
+        assert!(
+            matches!(actual, Err(Error::MaxDepthExceeded { limit: 1 })),
+            "Expected the depth limit to be hit"
+        );
+    }
+
+    #[test]
+    fn nesting_limit_does_not_count_the_wrappers() {
+        let options = ConvertOptions {
+            max_depth: Some(2),
+            wrap_in_macro: true,
+            wrap_roots: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options("<div><p>a</p></div>", &options);
+        assert_eq!(
+            actual.expect("Failed to convert html"),
+            "rsx! {\n    div {\n        p {\n            \"a\"\n        }\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn output_within_the_limit_is_converted() {
+        let options = ConvertOptions {
+            max_output_bytes: Some(16),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options("<div></div>", &options);
+        assert_eq!(actual.expect("Failed to convert html"), "div {}\n");
+    }
+
+    #[test]
+    fn empty_boolean_attributes_are_marked_as_true() {
+        let input = indoc! {r#"
+        <input required="" value="" />
+        "#};
+
+        let expected = indoc! {r#"
+        input {
+            required: true,
+            value: "",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn open_on_interactive_elements_is_marked_as_true() {
+        let input = indoc! {r#"
+        <div>
+            <dialog open>Hello</dialog>
+            <details open=""><summary>More</summary></details>
+        </div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            dialog {
+                open: true,
+                "Hello"
+            }
+            details {
+                open: true,
+                summary {
+                    "More"
+                }
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn presence_only_attributes_are_all_marked_as_true() {
+        let input = indoc! {r#"
+        <form novalidate="">
+            <select multiple="" required="" autofocus="" hidden></select>
+            <input readonly="" hidden="" required>
+        </form>
+        "#};
+
+        let expected = indoc! {"
+        form {
+            novalidate: true,
+            select {
+                autofocus: true,
+                hidden: true,
+                multiple: true,
+                required: true,
+            }
+            input {
+                hidden: true,
+                readonly: true,
+                required: true,
+            }
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn tags_can_be_renamed() {
+        let input = indoc! {r#"
+        <x-button button-type="submit">Go</x-button>
+        "#};
+
+        let expected = indoc! {r#"
+        Button {
+            button_type: "submit",
+            "Go"
+        }
+        "#};
+        let options = ConvertOptions {
+            tag_renames: HashMap::from([("x-button".to_string(), "Button".to_string())]),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn keyed_list_items_keep_their_keys_and_order() {
+        let input = indoc! {r#"
+        <ul><li key="1">a</li><li key="2">b</li></ul>
+        "#};
+
+        let expected = indoc! {r#"
+        ul {
+            li {
+                key: "1",
+                "a"
+            }
+            li {
+                key: "2",
+                "b"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn select_with_selected_option() {
+        let input = indoc! {r#"
+        <select name="letter"><option value="a" selected>A</option><option value="b">B</option></select>
+        "#};
+
+        let expected = indoc! {r#"
+        select {
+            name: "letter",
+            option {
+                selected: true,
+                value: "a",
+                "A"
+            }
+            option {
+                value: "b",
+                "B"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn filtered_attributes_are_dropped() {
+        let input = indoc! {r#"
+        <a href="/x" onclick="track()" onmouseover="track()" data-track-id="1" data-id="2">Go</a><span data-track-x="y"></span>
+        "#};
+
+        let expected = indoc! {r#"
+        a {
+            "data-id": "2",
+            href: "/x",
+            "Go"
+        }
+        span {}
+        "#};
+        let options = ConvertOptions {
+            attribute_filter: vec!["on*".to_string(), "data-track-*".to_string()],
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn leptos_target() {
+        let input = indoc! {r#"
+        <div class="example" id="id"><!-- note --><p onclick="go()">Some "text"</p><input required="" disabled /></div>
+        "#};
+
+        let expected = indoc! {r#"
+        <div class="example" id="id">
+            // note
+            <p on:click=move |_| { /* go() */ }>
+                "Some \"text\""
+            </p>
+            <input disabled=true required=true/>
+        </div>
+        "#};
+        let options = ConvertOptions {
+            target: Target::Leptos,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn leptos_attributes_keep_their_comments() {
+        let input = indoc! {r#"
+        <input value="{{count}}" name="n" />
+        "#};
+
+        let expected = indoc! {r#"
+        <input name="n" /* template expression, this may need to be a binding */ value="{{count}}"/>
+        "#};
+        let options = ConvertOptions {
+            target: Target::Leptos,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn whitespace_between_blocks_is_dropped() {
+        let input = indoc! {"
+        <div>
+            <p>One</p>
+            <p>Two</p>
+        </div>
+        "};
+
+        let expected = indoc! {r#"
+        div {
+            p {
+                "One"
+            }
+            p {
+                "Two"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn whitespace_is_kept_where_styled_to_be_preserved() {
+        let input = indoc! {r#"
+        <div style="white-space: pre">  spaced  </div>
+        <div style="color: red; white-space: pre-wrap"> <p>a</p> </div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            style: "white-space: pre",
+            "  spaced  "
+        }
+        div {
+            style: "color: red; white-space: pre-wrap",
+            " "
+            p {
+                "a"
+            }
+            " "
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn whitespace_is_kept_where_styled_after_a_valueless_attribute() {
+        let input = indoc! {r#"
+        <div hidden style="white-space: pre">  spaced  </div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            hidden: true,
+            style: "white-space: pre",
+            "  spaced  "
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn whitespace_is_kept_in_pre() {
+        let input = "<pre> <b>a</b>\n</pre>";
+
+        let expected = indoc! {r#"
+        pre {
+            " "
+            b {
+                "a"
+            }
+            "\n"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn whitespace_between_inline_elements_is_kept() {
+        let input = indoc! {"
+        <p><b>One</b> <i>Two</i></p>
+        "};
+
+        let expected = indoc! {r#"
+        p {
+            b {
+                "One"
+            }
+            " "
+            i {
+                "Two"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn whitespace_between_inline_elements_collapses_to_a_space() {
+        let input = "<p>\n    <b>One</b> \t <i>Two</i>\n</p><span> <b>x</b> </span>";
+
+        let expected = indoc! {r#"
+        p {
+            b {
+                "One"
+            }
+            " "
+            i {
+                "Two"
+            }
+        }
+        span {
+            b {
+                "x"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn class_tokens_can_be_sorted() {
+        let input = indoc! {r#"
+        <div id="x" class="c a  b"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            class: "a b c",
+            id: "x",
+        }
+        "#};
+        let options = ConvertOptions {
+            sort_class_tokens: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn attribute_values_can_be_trimmed() {
+        let input = indoc! {r#"
+        <input class=" x " value=" y ">
+        "#};
+
+        let expected = indoc! {r#"
+        input {
+            class: "x",
+            value: "y",
+        }
+        "#};
+        let options = ConvertOptions {
+            trim_attribute_values: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn numbers_can_be_unquoted() {
+        let input = indoc! {r#"
+        <input type="number" min="-1" max="10.5" step="any" value="2">
+        "#};
+
+        let expected = indoc! {r#"
+        input {
+            max: 10.5,
+            min: -1,
+            step: "any",
+            r#type: "number",
+            value: "2",
+        }
+        "#};
+        let options = ConvertOptions {
+            unquote_numbers: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn numbers_too_big_for_a_literal_stay_quoted() {
+        let input = indoc! {r#"
+        <input min="-9223372036854775808" max="9223372036854775808" step="1e999">
+        "#};
+
+        let expected = indoc! {r#"
+        input {
+            max: "9223372036854775808",
+            min: -9223372036854775808,
+            step: "1e999",
+        }
+        "#};
+        let options = ConvertOptions {
+            unquote_numbers: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn numbers_which_would_lose_zeros_stay_quoted() {
+        let input = indoc! {r#"
+        <td id="007" title="1.50" colspan="02" width="1.50"></td>
+        "#};
+
+        let expected = indoc! {r#"
+        td {
+            colspan: "02",
+            id: "007",
+            title: "1.50",
+            width: "1.50",
+        }
+        "#};
+        let options = ConvertOptions {
+            unquote_numbers: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn class_tokens_can_be_deduped() {
+        let input = indoc! {r#"
+        <div class="b a b c a"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            class: "b a c",
+        }
+        "#};
+        let options = ConvertOptions {
+            dedupe_class_tokens: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn inspect_lists_unsupported_constructs() {
+        let input = indoc! {r#"
+        <div><script>alert("hi")</script><!--[if IE]><p>Old</p><![endif]--><x-button></x-button></div>
+        "#};
+
+        let actual = inspect(input);
+        assert_eq!(
+            actual.expect("Failed to inspect html"),
+            vec![
+                Unsupported {
+                    kind: UnsupportedKind::Script,
+                    offset: 5,
+                },
+                Unsupported {
+                    kind: UnsupportedKind::ConditionalComment,
+                    offset: 33,
+                },
+                Unsupported {
+                    kind: UnsupportedKind::InvalidTagName,
+                    offset: 67,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn inspect_accepts_self_closing_tags() {
+        let actual = inspect("<div><br/><img/></div>");
+        assert_eq!(actual.expect("Failed to inspect html"), vec![]);
+    }
+
+    #[test]
+    fn inspect_accepts_namespaced_tags() {
+        let actual = inspect(r#"<svg:svg><svg:rect width="1"></svg:rect></svg:svg>"#);
+        assert_eq!(actual.expect("Failed to inspect html"), vec![]);
+    }
+
+    #[test]
+    fn inspect_finds_cdata() {
+        let input = "  <div><![CDATA[x]]></div>";
+
+        let actual = inspect(input);
+        assert_eq!(
+            actual.expect("Failed to inspect html"),
+            vec![Unsupported {
+                kind: UnsupportedKind::CData,
+                offset: 7,
+            }]
+        );
+    }
+
+    #[test]
+    fn literal_booleans_are_not_strings() {
+        let input = indoc! {r#"
+        <div draggable="true" spellcheck="false" title="true"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            draggable: true,
+            spellcheck: false,
+            title: "true",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn autofocus_is_a_boolean_with_or_without_a_value() {
+        let input = indoc! {r#"
+        <input autofocus="" /><input autofocus="false" />
+        "#};
+
+        let expected = indoc! {"
+        input {
+            autofocus: true,
+        }
+        input {
+            autofocus: false,
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn prefixed_values_are_emitted_as_expressions() {
+        let input = indoc! {r#"
+        <input value="rsx:count()" name="rsx-name" />
+        "#};
+
+        let expected = indoc! {r#"
+        input {
+            name: "rsx-name",
+            value: count(),
+        }
+        "#};
+        let options = ConvertOptions {
+            expression_prefix: Some(String::from("rsx:")),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn prefixes_without_an_expression_are_strings() {
+        let input = indoc! {r#"
+        <input value="rsx:" name="rsx: " />
+        "#};
+
+        let expected = indoc! {r#"
+        input {
+            name: "rsx: ",
+            value: "rsx:",
+        }
+        "#};
+        let options = ConvertOptions {
+            expression_prefix: Some(String::from("rsx:")),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn template_expressions_are_flagged() {
+        let input = indoc! {r#"
+        <input value="{{count}}" />
+        "#};
+
+        let expected = indoc! {r#"
+        input {
+            // template expression, this may need to be a binding
+            value: "{{count}}",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn template_expressions_can_be_interpolated() {
+        let input = indoc! {r#"
+        <input value="{{ count }}" />
+        "#};
+
+        let expected = indoc! {r#"
+        input {
+            value: "{count}",
+        }
+        "#};
+        let options = ConvertOptions {
+            template_expressions: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn anchor_with_href_target_and_event() {
+        let input = indoc! {r#"
+        <a onclick="nav()" target="_blank" href="/x">Go</a>
+        "#};
+
+        let expected = indoc! {r#"
+        a {
+            href: "/x",
+            target: "_blank",
+            onclick: move |_| { /* nav() */ },
+            "Go"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn event_handlers_without_a_value_are_still_closures() {
+        let input = indoc! {"
+        <a onclick>Go</a>
+        "};
+
+        let expected = indoc! {r#"
+        a {
+            onclick: move |_| {},
+            "Go"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn event_handlers_can_keep_their_javascript_in_a_comment() {
+        let input = indoc! {r#"
+        <button onclick="go('/*')">Go</button>
+        "#};
+
+        let expected = indoc! {r#"
+        button {
+            onclick: move |_| { /* go('/ *') */ },
+            "Go"
+        }
+        "#};
+        let options = ConvertOptions {
+            js_handler_style: JsHandlerStyle::Commented,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn event_handlers_can_be_empty_stubs() {
+        let input = indoc! {r#"
+        <button onclick="go()">Go</button>
+        "#};
+
+        let expected = indoc! {r#"
+        button {
+            onclick: move |_| {},
+            "Go"
+        }
+        "#};
+        let options = ConvertOptions {
+            js_handler_style: JsHandlerStyle::Empty,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn form_with_method_action_and_submit_handler() {
+        let input = indoc! {r#"
+        <form onsubmit="validate()" method="post" action="/submit">
+            <button type="submit">Send</button>
+        </form>
+        "#};
+
+        let expected = indoc! {r#"
+        form {
+            action: "/submit",
+            method: "post",
+            onsubmit: move |_| { /* validate() */ },
+            button {
+                r#type: "submit",
+                "Send"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn short_tags_can_be_collapsed() {
+        let input = indoc! {r#"
+        <div><img src="a.png" alt="x"><img src="a-very-long-path-to-an-image.png" alt="A long description of the image"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            img { alt: "x", src: "a.png" }
+            img {
+                alt: "A long description of the image",
+                src: "a-very-long-path-to-an-image.png",
+            }
+        }
+        "#};
+        let options = ConvertOptions {
+            collapse_short_tags: true,
+            max_width: 60,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn components_can_be_qualified_with_a_module() {
+        let input = indoc! {"
+        <Button/>
+        "};
+
+        let expected = indoc! {"
+        ui::Button {}
+        "};
+        let options = ConvertOptions {
+            component_module_prefix: Some(String::from("ui")),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn tag_namespaces_are_stripped() {
+        let input = indoc! {r#"
+        <svg:rect width="1" />
+        "#};
+
+        let expected = indoc! {r#"
+        rect {
+            width: "1",
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn namespaced_tags_can_be_renamed() {
+        let input = indoc! {"
+        <svg:rect /><svg:circle />
+        "};
+
+        let expected = indoc! {"
+        SvgRect {}
+        svg:circle {}
+        "};
+        let options = ConvertOptions {
+            tag_namespaces: TagNamespaces::Keep,
+            tag_renames: HashMap::from([("svg:rect".to_string(), "SvgRect".to_string())]),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn fragments_can_be_debugged() {
+        assert_eq!(format!("{:?}", Fragment::ClosingBrace), "ClosingBrace");
+        assert_eq!(
+            format!("{:?}", Fragment::ClosingTag("div")),
+            "ClosingTag(\"div\")"
+        );
+    }
+
+    #[test]
+    fn text_can_be_braced() {
+        let input = indoc! {"
+        <p>Hello <b>{name}</b></p>
+        "};
+
+        let expected = indoc! {r#"
+        p {
+            {"Hello "}
+            b {
+                {"{name}"}
+            }
+        }
+        "#};
+        let options = ConvertOptions {
+            text_child_style: TextChildStyle::Braced,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn text_is_bare_by_default() {
+        let input = indoc! {"
+        <p>Hello <b>world</b></p>
+        "};
+
+        let expected = indoc! {r#"
+        p {
+            "Hello "
+            b {
+                "world"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn text_with_braces_is_flagged() {
+        let input = indoc! {"
+        <code>fn main() {}</code>
+        "};
+
+        let expected = indoc! {r#"
+        code {
+            // contains braces, which rsx will treat as interpolation
+            "fn main() {}"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn text_with_braces_can_be_escaped() {
+        let input = indoc! {"
+        <code>fn main() {}</code>
+        "};
+
+        let expected = indoc! {r#"
+        code {
+            "fn main() {{}}"
+        }
+        "#};
+        let options = ConvertOptions {
+            text_braces: TextBraces::Escape,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn label_wrapping_an_input() {
+        let input = indoc! {r#"
+        <label>Name <input type="text"></label>
+        "#};
+
+        let expected = indoc! {r#"
+        label {
+            "Name "
+            input {
+                r#type: "text",
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn converts_asynchronously() {
+        let actual = convert_async("<div></div>").await;
+        assert_eq!(actual.expect("Failed to convert html"), "div {}\n");
+    }
+
+    #[test]
+    fn cached_converter_remembers_results() {
+        let mut converter = CachedConverter::new();
+        let options = ConvertOptions::default();
+
+        let first = converter.convert("<div></div>", &options);
+        let second = converter.convert("<div></div>", &options);
+        assert_eq!(
+            first.expect("Failed to convert html"),
+            second.expect("Failed to convert html")
+        );
+        assert_eq!(converter.len(), 1);
+
+        let ascii_only = ConvertOptions {
+            ascii_only: true,
+            ..ConvertOptions::default()
+        };
+        let third = converter.convert("<div></div>", &ascii_only);
+        assert_eq!(third.expect("Failed to convert html"), "div {}\n");
+        assert_eq!(converter.len(), 2);
+    }
+
+    #[cfg(feature = "validate")]
+    #[test]
+    fn validated_output_parses() {
+        let actual = convert_validated("<div class=\"x\"><input type=\"text\"></div>");
+        assert!(actual.is_ok(), "Expected valid rsx, got {actual:?}");
+    }
+
+    #[cfg(feature = "validate")]
+    #[test]
+    fn validated_output_rejects_invalid_rsx() {
+        let actual = convert_validated("<1abc></1abc>");
+        assert!(
+            matches!(actual, Err(Error::InvalidRsx { .. })),
+            "Expected invalid rsx, got {actual:?}"
+        );
+    }
+
+    #[cfg(feature = "validate")]
+    #[test]
+    fn const_output_parses_back_to_the_rsx() {
+        let input = "<p class=\"x\">a\\b \u{e9}</p>";
+
+        let actual = convert_to_const(input, "RSX").expect("Failed to convert html");
+        let literal = actual
+            .strip_prefix("pub const RSX: &str = ")
+            .and_then(|rest| rest.strip_suffix(";\n"))
+            .unwrap_or_default();
+        let parsed = syn::parse_str::<syn::LitStr>(literal).map(|literal| literal.value());
+        assert_eq!(
+            parsed.ok(),
+            convert(input).ok(),
+            "Expected the const to hold the rsx"
+        );
+    }
+
+    #[test]
+    fn tables_are_not_given_a_tbody() {
+        let input = indoc! {"
+        <table>
+            <tr><td>x</td></tr>
+        </table>
+        "};
+
+        let expected = indoc! {"
+        table {
+            tr {
+                td {
+                    \"x\"
+                }
+            }
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn tables_keep_an_explicit_tbody() {
+        let input = indoc! {"
+        <table>
+            <tbody><tr><td>x</td></tr></tbody>
+        </table>
+        "};
+
+        let expected = indoc! {"
+        table {
+            tbody {
+                tr {
+                    td {
+                        \"x\"
+                    }
+                }
+            }
+        }
+        "};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn svg_use_with_xlink_href() {
+        let input = indoc! {r##"
+        <svg><use xlink:href="#a"/></svg>
+        "##};
+
+        let expected = indoc! {r##"
+        svg {
+            r#use {
+                "xlink:href": "#a",
+            }
+        }
+        "##};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn svg_namespace_declarations_can_be_dropped() {
+        let input = indoc! {r#"
+        <svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="8"></svg>
+        "#};
+
+        let expected = indoc! {r#"
+        svg {
+            width: "8",
+        }
+        "#};
+        let options = ConvertOptions {
+            drop_svg_xmlns: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn table_with_headers_and_spans() {
+        let input = indoc! {r#"
+        <table>
+            <caption>Scores</caption>
+            <thead>
+                <tr><th scope="col" colspan="2">Name</th><th scope="col">Score</th></tr>
+            </thead>
+            <tbody>
+                <tr><th scope="row" rowspan="2">Ada</th><td>Lovelace</td><td>10</td></tr>
+            </tbody>
+        </table>
+        "#};
+
+        let expected = indoc! {r#"
+        table {
+            caption {
+                "Scores"
+            }
+            thead {
+                tr {
+                    th {
+                        colspan: 2,
+                        scope: "col",
+                        "Name"
+                    }
+                    th {
+                        scope: "col",
+                        "Score"
+                    }
+                }
+            }
+            tbody {
+                tr {
+                    th {
+                        rowspan: 2,
+                        scope: "row",
+                        "Ada"
+                    }
+                    td {
+                        "Lovelace"
+                    }
+                    td {
+                        "10"
+                    }
+                }
+            }
+        }
+        "#};
+        let options = ConvertOptions {
+            unquote_numbers: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn picture_with_sources() {
+        let input = indoc! {r#"
+        <picture><source srcset="a.webp" type="image/webp"><source srcset="a.avif" type="image/avif"><img src="a.jpg" alt="A"></picture>
+        "#};
+
+        let expected = indoc! {r#"
+        picture {
+            source {
+                srcset: "a.webp",
+                r#type: "image/webp",
+            }
+            source {
+                srcset: "a.avif",
+                r#type: "image/avif",
+            }
+            img {
+                alt: "A",
+                src: "a.jpg",
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn picture_with_media_queries() {
+        let input = indoc! {r#"
+        <picture>
+            <source media="(min-width: 800px)" srcset="large.png 1x, large@2x.png 2x">
+            <img src="small.png" sizes="(max-width: 600px) 480px, 800px">
+        </picture>
+        "#};
+
+        let expected = indoc! {r#"
+        picture {
+            source {
+                media: "(min-width: 800px)",
+                srcset: "large.png 1x, large@2x.png 2x",
+            }
+            img {
+                sizes: "(max-width: 600px) 480px, 800px",
+                src: "small.png",
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn whitespace_around_a_picture_is_kept() {
+        let input = indoc! {r#"
+        <p><b>See</b> <picture><img src="a.png"></picture> <i>here</i></p>
+        "#};
+
+        let expected = indoc! {r#"
+        p {
+            b {
+                "See"
+            }
+            " "
+            picture {
+                img {
+                    src: "a.png",
+                }
+            }
+            " "
+            i {
+                "here"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn attributes_and_children_can_be_indented_differently() {
+        let input = indoc! {r#"
+        <div class="a"><p id="b">Text</p></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+          class: "a",
+            p {
+              id: "b",
+                "Text"
+            }
+        }
+        "#};
+        let options = ConvertOptions {
+            attribute_indent: 2,
+            child_indent: 4,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn results_include_the_options_a_preset_expanded_into() {
+        let input = indoc! {"
+        <div><img src=\"a.png\"/></div>
+        "};
+
+        let expected = indoc! {"
+        div {
+          img { src: \"a.png\" }
+        }
+        "};
+        let actual = convert_to_result(input, Preset::Compact).expect("Failed to convert html");
+        assert_eq!(actual.output, expected);
+        assert_eq!(actual.options, ConvertOptions::from(Preset::Compact));
+        assert_eq!(actual.options.child_indent, 2);
+        assert!(actual.options.collapse_short_tags);
+    }
+
+    #[test]
+    fn results_list_renamed_attributes() {
+        let input = indoc! {r#"
+        <svg viewBox="0 0 8 8" class="icon"><path d="M0 0"/></svg>
+        <label for="a">A</label>
+        <svg viewBox="0 0 8 8"></svg>
+        "#};
+
+        let actual =
+            convert_to_result(input, ConvertOptions::default()).expect("Failed to convert html");
+        assert_eq!(
+            actual.renamed_attributes,
+            vec![
+                (String::from("viewBox"), String::from("view_box")),
+                (String::from("for"), String::from("r#for")),
+            ]
+        );
+    }
+
+    #[test]
+    fn top_level_elements_can_be_written_to_a_writer() {
+        let input = indoc! {"
+        <p>One</p>
+        <!-- between -->
+        <p>Two</p>
+        <hr />
+        "};
+
+        let expected = indoc! {"
+        p {
+            \"One\"
+        }
+        // between
+        p {
+            \"Two\"
+        }
+        hr {}
+        "};
+        let mut writer = Vec::new();
+        convert_to_writer(input, &ConvertOptions::default(), &mut writer)
+            .expect("Failed to convert html");
+        assert_eq!(String::from_utf8_lossy(&writer), expected);
+    }
+
+    #[test]
+    fn wrapped_top_level_elements_are_handed_over_as_they_complete() {
+        let options = ConvertOptions {
+            wrap_in_macro: true,
+            wrap_roots: true,
+            ..ConvertOptions::default()
+        };
+        let mut pieces = Vec::new();
+        convert_in_pieces("<hr /><br />", &options, |piece| {
+            pieces.push(piece.to_string());
+            Ok(())
+        })
+        .expect("Failed to convert html");
+        assert_eq!(
+            pieces,
+            [
+                "rsx! {\n    Fragment {\n",
+                "        hr {}\n",
+                "        br {}\n",
+                "    }\n",
+                "}\n"
+            ]
+        );
+    }
+
+    #[test]
+    fn roots_including_text_can_be_wrapped_in_a_fragment() {
+        let input = indoc! {"
+        Hello <b>world</b>
+        "};
+
+        let expected = indoc! {"
+        Fragment {
+            \"Hello \"
+            b {
+                \"world\"
+            }
+        }
+        "};
+        let options = ConvertOptions {
+            wrap_roots: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn single_roots_are_not_wrapped() {
+        let input = indoc! {"
+        <b>world</b>
+        "};
+
+        let expected = indoc! {"
+        b {
+            \"world\"
+        }
+        "};
+        let options = ConvertOptions {
+            wrap_roots: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn charset_and_viewport_metas() {
+        let input = indoc! {r#"
+        <head>
+            <meta charset="utf-8">
+            <meta name="viewport" content="width=device-width, initial-scale=1">
+        </head>
+        "#};
+
+        let expected = indoc! {r#"
+        head {
+            meta {
+                charset: "utf-8",
+            }
+            meta {
+                content: "width=device-width, initial-scale=1",
+                name: "viewport",
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn components_can_be_scaffolded_for_dioxus_0_5() {
+        let input = indoc! {"
+        <p>Hi</p>
+        "};
+
+        let expected = indoc! {"
+        fn App() -> Element {
+            rsx! {
+                p {
+                    \"Hi\"
+                }
+            }
+        }
+        "};
+        let actual = convert_to_component(input, "App", &ConvertOptions::default());
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn components_can_be_scaffolded_for_dioxus_0_4() {
+        let input = indoc! {"
+        <p>Hi</p>
+        "};
+
+        let expected = indoc! {"
+        fn App(cx: Scope) -> Element {
+            cx.render(rsx! {
+                p {
+                    \"Hi\"
+                }
+            })
+        }
+        "};
+        let options = ConvertOptions {
+            dioxus_version: DioxusVersion::V0_4,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_to_component(input, "App", &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn component_names_must_be_identifiers() {
+        for name in ["my-app", "", "fn", "2App"] {
+            let actual = convert_to_component("<p>a</p>", name, &ConvertOptions::default());
+            assert!(
+                matches!(&actual, Err(Error::InvalidComponentName { name: given }) if given == name),
+                "Expected {name:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn output_can_be_wrapped_in_the_dioxus_0_4_render_call() {
+        let input = indoc! {"
+        <p>a</p>
+        <p>b</p>
+        "};
+
+        let expected = indoc! {"
+        cx.render(rsx! {
+            Fragment {
+                p {
+                    \"a\"
+                }
+                p {
+                    \"b\"
+                }
+            }
+        })
+        "};
+        let options = ConvertOptions {
+            dioxus_version: DioxusVersion::V0_4,
+            wrap_in_macro: true,
+            wrap_roots: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options).expect("Failed to convert html");
+        assert_eq!(actual.matches("cx.render(").count(), 1);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn output_can_be_wrapped_in_the_rsx_macro() {
+        let input = indoc! {"
+        <p>a</p>
+        "};
+
+        let expected = indoc! {"
+        rsx! {
+            p {
+                \"a\"
+            }
+        }
+        "};
+        let options = ConvertOptions {
+            wrap_in_macro: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn anchors_without_attributes_only_hold_their_text() {
+        let input = indoc! {"
+        <a>click</a>
+        "};
+
+        let expected = indoc! {r#"
+        a {
+            "click"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn links_within_svg_are_flagged() {
+        let input = indoc! {r#"
+        <div><a href="/home">Home</a><svg><a href="/home"><circle r="4"></circle></a></svg><a href="/away">Away</a></div>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            a {
+                href: "/home",
+                "Home"
+            }
+            svg {
+                a {
+                    // dioxus creates `a` as an html element, so this link won't render within svg
+                    href: "/home",
+                    circle {
+                        r: "4",
+                    }
+                }
+            }
+            a {
+                href: "/away",
+                "Away"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn text_of_only_an_entity_is_decoded() {
+        let input = indoc! {"
+        <p><span>&nbsp;</span> <span>&#160;</span> <span>&amp;</span></p>
+        "};
+
+        let expected = indoc! {r#"
+        p {
+            span {
+                "\u{a0}"
             }
-            Fragment::TlNode(Node::Comment(comment)) => {
-                out.push_str(indentation_spaces(indentation_level).as_ref());
-                out.push_str("// ");
-                out.push_str(
-                    comment
-                        .try_as_utf8_str()
-                        .unwrap_or_default()
-                        .trim_start_matches("<!-- ")
-                        .trim_end_matches(" -->"),
-                );
-                out.push('\n');
+            " "
+            span {
+                "\u{a0}"
             }
-            Fragment::ClosingBrace => {
-                indentation_level -= 1;
-                out.push_str(indentation_spaces(indentation_level).as_ref());
-                out.push_str("}\n");
+            " "
+            span {
+                "&"
             }
         }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
-    Ok(out)
-}
+    #[test]
+    fn non_breaking_spaces_are_not_collapsed() {
+        let input = "<div><p>a</p>\u{a0}<p>b</p></div>";
 
-fn indentation_spaces(indentation_level: usize) -> String {
-    " ".repeat(indentation_level * INDENTATION)
-}
+        let expected = indoc! {r#"
+        div {
+            p {
+                "a"
+            }
+            "\u{a0}"
+            p {
+                "b"
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
 
-#[cfg(test)]
-mod tests {
-    use indoc::indoc;
+    #[test]
+    fn percent_encoding_is_left_as_written() {
+        let input = indoc! {r#"
+        <a href="/search?q=a%20b%26c">50%25 &amp; more</a>
+        "#};
 
-    use super::*;
+        let expected = indoc! {r#"
+        a {
+            href: "/search?q=a%20b%26c",
+            "50%25 & more"
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
 
     #[test]
-    fn empty_div() {
+    fn unknown_entities_are_left_as_written() {
         let input = indoc! {"
-        <div></div>
+        <p>fish &chips; &amp bread &#xffffffff;</p>
         "};
 
-        let expected = indoc! {"
-        div {}
-        "};
+        let expected = indoc! {r#"
+        p {
+            "fish &chips; &amp bread &#xffffffff;"
+        }
+        "#};
         let actual = convert(input);
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
     #[test]
-    fn empty_span() {
+    fn each_document_is_converted_separately() {
+        let options = ConvertOptions {
+            empty_tag_names: EmptyTagNames::Error,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_many_with_options(&["<p>a</p>", "<div><></div>", "<br />"], &options);
+
+        assert_eq!(actual.len(), 3, "Expected a result for each document");
+        assert!(
+            matches!(&actual[1], Err(Error::EmptyTagName { .. })),
+            "Expected the malformed document to fail"
+        );
+        let converted = actual
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect::<Vec<_>>();
+        assert_eq!(converted, ["p {\n    \"a\"\n}\n", "br {}\n"]);
+    }
+
+    #[test]
+    fn top_level_siblings_can_be_separated_by_a_blank_line() {
         let input = indoc! {"
-        <span></span>
+        <header><p>a</p><p>b</p></header>
+        <main></main>
+        <footer></footer>
         "};
 
-        let expected = indoc! {"
-        span {}
-        "};
-        let actual = convert(input);
+        let expected = indoc! {r#"
+        header {
+            p {
+                "a"
+            }
+            p {
+                "b"
+            }
+        }
+
+        main {}
+
+        footer {}
+        "#};
+        let options = ConvertOptions {
+            blank_line_between_siblings: BlankLines::TopLevel,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
     #[test]
-    fn self_closing_div() {
+    fn siblings_at_every_level_can_be_separated_by_a_blank_line() {
         let input = indoc! {"
-        <div />
+        <div><p>a</p><p>b</p>text<hr /></div>
         "};
 
-        let expected = indoc! {"
-        div {}
-        "};
+        let expected = indoc! {r#"
+        div {
+            p {
+                "a"
+            }
+
+            p {
+                "b"
+            }
+            "text"
+            hr {}
+        }
+        "#};
+        let options = ConvertOptions {
+            blank_line_between_siblings: BlankLines::Everywhere,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn media_elements_with_sources() {
+        let input = indoc! {r#"
+        <video controls loop><source src="x.mp4" type="video/mp4"></video>
+        "#};
+
+        let expected = indoc! {r#"
+        video {
+            controls: true,
+            r#loop: true,
+            source {
+                src: "x.mp4",
+                r#type: "video/mp4",
+            }
+        }
+        "#};
         let actual = convert(input);
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
     #[test]
-    fn comments_become_rust_comments() {
-        let input = indoc! {"
-        <div><!-- nothing in here --></div><!-- nothing out here -->
-        "};
+    fn audio_with_several_sources() {
+        let input = indoc! {r#"
+        <audio autoplay muted controls><source src="a.ogg" type="audio/ogg"><source src="a.mp3"></audio>
+        "#};
 
-        let expected = indoc! {"
-        div {
-            // nothing in here
+        let expected = indoc! {r#"
+        audio {
+            autoplay: true,
+            controls: true,
+            muted: true,
+            source {
+                src: "a.ogg",
+                r#type: "audio/ogg",
+            }
+            source {
+                src: "a.mp3",
+            }
         }
-        // nothing out here
-        "};
+        "#};
         let actual = convert(input);
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
     #[test]
-    fn div_with_attributes() {
+    fn source_map_links_elements_to_their_rsx() {
+        let input = indoc! {r#"
+        <div id="a">
+            <p>text</p>
+            <hr />
+        </div>
+        "#};
+
+        let (output, source_map) = convert_with_source_map(input).expect("Failed to convert html");
+        let lines = output.lines().collect::<Vec<_>>();
+        let spans = source_map
+            .iter()
+            .map(|entry| {
+                (
+                    &input[entry.source.clone()],
+                    lines[entry.output_lines.clone()].join("\n"),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            spans,
+            [
+                (input.trim_end(), output.trim_end().to_string()),
+                (
+                    "<p>text</p>",
+                    "    p {\n        \"text\"\n    }".to_string()
+                ),
+                ("<hr />", "    hr {}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn code_blocks_are_kept_as_written() {
         let input = indoc! {"
-        <div class=\"example\"></div>
+        <pre><code>for i in range(n):
+            if a &lt; b &amp;&amp; c:
+                <b>print</b> <i>(i)</i></code></pre>
         "};
 
-        let expected = indoc! {"
-        div {
-            class: \"example\",
+        let expected = indoc! {r#"
+        pre {
+            code {
+                "for i in range(n):\n    if a < b && c:\n        "
+                b {
+                    "print"
+                }
+                " "
+                i {
+                    "(i)"
+                }
+            }
         }
-        "};
+        "#};
         let actual = convert(input);
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
     #[test]
-    fn div_with_multiple_attributes() {
+    fn root_attributes_are_indented_inside_the_macro() {
+        let input = indoc! {r#"
+        <div class="x"></div>
+        "#};
+
+        let expected = indoc! {r#"
+        rsx! {
+            div {
+                class: "x",
+            }
+        }
+        "#};
+        let options = ConvertOptions {
+            wrap_in_macro: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options).expect("Failed to convert html");
+        assert!(
+            actual.lines().any(|line| line == "        class: \"x\","),
+            "Expected the attribute to be two levels deep"
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn elements_can_be_converted_to_one_line() {
+        let actual = convert_inline(r#"<div class="x">hi</div>"#);
+        assert_eq!(
+            actual.expect("Failed to convert html"),
+            r#"div { class: "x", "hi" }"#
+        );
+    }
+
+    #[test]
+    fn nested_elements_and_comments_stay_on_one_line() {
+        let input = indoc! {r#"
+        <ul>
+            <!-- items -->
+            <li><input type="checkbox" checked></li>
+            <li></li>
+        </ul>
+        "#};
+
+        let actual = convert_inline(input);
+        assert_eq!(
+            actual.expect("Failed to convert html"),
+            r#"ul { /* items */ li { input { checked: true, r#type: "checkbox" } } li {} }"#
+        );
+    }
+
+    #[test]
+    fn closing_brace_aligns_after_a_trailing_comment() {
         let input = indoc! {"
-        <div class=\"example\" id=\"id\"></div>
+        <div><span></span><!-- last --></div>
         "};
 
         let expected = indoc! {"
         div {
-            class: \"example\",
-            id: \"id\",
+            span {}
+            // last
         }
         "};
         let actual = convert(input);
@@ -262,65 +4013,205 @@ mod tests {
     }
 
     #[test]
-    fn div_with_irregular_attributes() {
+    fn expressions_matching_their_attribute_use_shorthand() {
         let input = indoc! {r#"
-        <div stroke-linecap="round" ></div>
+        <input value="rsx:value" name="rsx:label" checked="rsx:checked">
         "#};
 
         let expected = indoc! {"
-        div {
-            stroke_linecap: \"round\",
+        input {
+            checked,
+            name: label,
+            value,
         }
         "};
-        let actual = convert(input);
+        let options = ConvertOptions {
+            expression_prefix: Some(String::from("rsx:")),
+            shorthand_attributes: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
     #[test]
-    fn div_with_inner_text() {
-        let input = indoc! {"
-        <div>Some text</div>
-        "};
+    fn image_loading_attributes() {
+        let input = indoc! {r#"
+        <img src="a.png" loading="lazy" decoding="async" fetchpriority="high" referrerPolicy="no-referrer">
+        "#};
 
-        let expected = indoc! {"
-        div {
-            \"Some text\"
+        let expected = indoc! {r#"
+        img {
+            decoding: "async",
+            fetchpriority: "high",
+            loading: "lazy",
+            referrerpolicy: "no-referrer",
+            src: "a.png",
         }
-        "};
+        "#};
         let actual = convert(input);
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
     #[test]
-    fn non_snake_capitalised_attributes_are_converted_to_snake() {
+    fn elements_with_only_text_can_be_folded_onto_one_line() {
         let input = indoc! {r#"
-        <div SomeAttribute="door"></div>
+        <head>
+            <title>HTML Tutorial</title>
+            <style>body { color: red; }</style>
+            <script src="x.js">go()</script>
+            <p>This is a much longer piece of text which won't fit within the maximum width once folded up</p>
+        </head>
+        "#};
+
+        let expected = indoc! {r#"
+        head {
+            title { "HTML Tutorial" }
+            style {
+                // contains braces, which rsx will treat as interpolation
+                "body { color: red; }"
+            }
+            script {
+                src: "x.js",
+                "go()"
+            }
+            p {
+                "This is a much longer piece of text which won't fit within the maximum width once folded up"
+            }
+        }
+        "#};
+        let options = ConvertOptions {
+            fold_text_leaves: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn chains_of_inline_elements_can_be_folded_onto_one_line() {
+        let input = indoc! {r#"
+        <div>
+            <p><strong><em>text</em></strong></p>
+            <p><b><i class="x">styled</i></b></p>
+            <p><span><div>block</div></span></p>
+        </div>
         "#};
 
         let expected = indoc! {r#"
         div {
-            some_attribute: "door",
+            p {
+                strong { em { "text" } }
+            }
+            p {
+                b {
+                    i {
+                        class: "x",
+                        "styled"
+                    }
+                }
+            }
+            p {
+                span {
+                    div {
+                        "block"
+                    }
+                }
+            }
         }
         "#};
+        let options = ConvertOptions {
+            fold_inline_chains: true,
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn blocks_of_only_whitespace_are_empty() {
+        let input = "<div>\n   </div>";
+
         let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), "div {}\n");
+    }
+
+    #[test]
+    fn tags_whose_children_are_all_dropped_are_empty() {
+        let input = indoc! {r#"
+        <div class="x"><script>go()</script></div>
+        <p><script>go()</script></p>
+        "#};
+
+        let expected = indoc! {r#"
+        div {
+            class: "x",
+        }
+        p {}
+        "#};
+        let options = ConvertOptions {
+            drop_elements: HashSet::from([String::from("script")]),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
     #[test]
-    fn solo_attributes_are_marked_as_true() {
-        let input = indoc! {r"
-        <input disabled />
-        "};
+    fn elements_can_be_dropped_with_everything_in_them() {
+        let input = indoc! {r#"
+        <div>
+            <SCRIPT src="x.js"></SCRIPT>
+            <p>text<script>alert("hi")</script></p>
+            <iframe><p>nested</p></iframe>
+        </div>
+        "#};
 
-        let expected = indoc! {r"
-        input {
-            disabled: true,
+        let expected = indoc! {r#"
+        div {
+            p {
+                "text"
+            }
         }
-        "};
-        let actual = convert(input);
+        "#};
+        let options = ConvertOptions {
+            drop_elements: ["script", "iframe"].into_iter().map(String::from).collect(),
+            ..ConvertOptions::default()
+        };
+        let actual = convert_with_options(input, &options);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn output_can_be_written_as_a_const() {
+        let input = indoc! {r#"
+        <p class="x">a\b</p>
+        "#};
+
+        let expected = indoc! {r#"
+        pub const RSX: &str = "p {\n    class: \"x\",\n    \"a\\\\b\"\n}\n";
+        "#};
+        let actual = convert_to_const(input, "RSX");
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
+    #[test]
+    fn const_names_must_be_identifiers() {
+        for name in ["my-rsx", "", "type", "2RSX", "r#self"] {
+            let actual = convert_to_const("<p>a</p>", name);
+            assert!(
+                matches!(&actual, Err(Error::InvalidConstName { name: given }) if given == name),
+                "Expected {name:?} to be rejected"
+            );
+        }
+
+        let actual = convert_to_const("<br />", "r#type");
+        assert_eq!(
+            actual.expect("Failed to convert html"),
+            "pub const r#type: &str = \"br {}\\n\";\n"
+        );
+    }
+
     #[test]
     fn realistic_html() {
         let input = indoc! {"
@@ -350,6 +4241,53 @@ mod tests {
         assert_eq!(actual.expect("Failed to convert html"), expected);
     }
 
+    #[test]
+    fn document_element_attributes_are_kept() {
+        let input = indoc! {r#"
+        <html lang="en"><body id="b" class="a"><p>x</p></body></html>
+        "#};
+
+        let expected = indoc! {r#"
+        html {
+            lang: "en",
+            body {
+                class: "a",
+                id: "b",
+                p {
+                    "x"
+                }
+            }
+        }
+        "#};
+        let actual = convert(input);
+        assert_eq!(actual.expect("Failed to convert html"), expected);
+    }
+
+    #[test]
+    fn realistic_html_has_no_trailing_whitespace() {
+        let input = indoc! {"
+        <html><head><title>HTML Tutorial</title></head><body id=\"body\"><!-- --><h1>This is a heading</h1><p class=\"bold\">This is a paragraph.</p></body></html>
+        "};
+
+        let lines =
+            convert_lines(input, &ConvertOptions::default()).expect("Failed to convert html");
+        assert!(lines.iter().any(|line| line.trim() == "//"));
+        assert!(lines
+            .iter()
+            .all(|line| !line.ends_with(char::is_whitespace)));
+    }
+
+    #[test]
+    fn long_path_data_is_kept_intact() {
+        let path = format!("M0 0{}z", " l1.5-2.25 0,3e-2".repeat(2000));
+        let input = format!(r#"<path d="{path}" />"#);
+
+        let actual = convert(input.as_str()).expect("Failed to convert html");
+        assert_eq!(escape_string(&path, false), path);
+        assert!(actual.contains(&format!("d: \"{path}\",")));
+        assert!(actual.contains(" l1.5-2.25 0,3e-2z\","));
+    }
+
     #[test]
     fn realistic_svg() {
         let input = indoc! {r##"
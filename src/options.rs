@@ -0,0 +1,364 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// Options controlling how html is converted into rsx
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(
+    clippy::struct_excessive_bools,
+    reason = "each option is an independent switch, not a state machine"
+)]
+pub struct ConvertOptions {
+    /// Escape every non-ASCII character in emitted strings, string keys, and comments as a `\u{...}` escape
+    ///
+    /// Rust has no escapes for identifiers, so element names given in [`tag_renames`](Self::tag_renames) are written
+    /// as given; names read from html are always ASCII.
+    pub ascii_only: bool,
+    /// Abort the conversion once the output grows beyond this many bytes
+    pub max_output_bytes: Option<usize>,
+    /// Abort the conversion once elements are nested deeper than this many levels, where top level elements are at
+    /// level zero
+    pub max_depth: Option<usize>,
+    /// Emit tags under a different name, keyed by the original tag name, e.g. `x-button` to `Button`
+    pub tag_renames: HashMap<String, String>,
+    /// Drop elements with these tag names, given in lowercase, along with everything in them, e.g. `script`
+    pub drop_elements: HashSet<String>,
+    /// How to handle tag names with a namespace prefix, e.g. `svg:rect`; `tag_renames` are applied first
+    pub tag_namespaces: TagNamespaces,
+    /// How to handle tags without a name, which malformed html can leave
+    pub empty_tag_names: EmptyTagNames,
+    /// Drop attributes with these names; a trailing `*` matches any name starting with the rest, e.g. `on*`
+    pub attribute_filter: Vec<String>,
+    /// Drop `xmlns` and `xmlns:*` attributes from `svg` elements, as Dioxus namespaces svg itself
+    pub drop_svg_xmlns: bool,
+    /// Sort the tokens within `class` values, so reordering classes doesn't show up in diffs
+    pub sort_class_tokens: bool,
+    /// Drop repeated tokens within `class` values, keeping the first of each
+    pub dedupe_class_tokens: bool,
+    /// Emit `{{expression}}` placeholders from templating engines in attribute values as rsx interpolation,
+    /// rather than flagging them with a comment
+    pub template_expressions: bool,
+    /// How to write the javascript of event handler attributes, e.g. `onclick="go()"`, as rust closures
+    pub js_handler_style: JsHandlerStyle,
+    /// How to handle braces in text, which rsx would otherwise read as interpolation
+    pub text_braces: TextBraces,
+    /// How to write text children in Dioxus rsx
+    pub text_child_style: TextChildStyle,
+    /// The framework whose syntax to emit
+    pub target: Target,
+    /// The version of Dioxus to write macro calls and component signatures for
+    pub dioxus_version: DioxusVersion,
+    /// Wrap the output in the macro call rendering it, e.g. `rsx! { ... }`, or `cx.render(rsx! { ... })` for Dioxus 0.4
+    pub wrap_in_macro: bool,
+    /// The case element attribute names are written in as rsx keys; component props always keep their case
+    pub attribute_case: AttributeCase,
+    /// Write element attributes the converter doesn't know Dioxus has, e.g. `foobar`, as string keys, which Dioxus
+    /// accepts for any attribute, rather than identifiers which only compile for attributes it knows
+    pub unknown_attributes_as_strings: bool,
+    /// Whether attributes go before or after the children of a tag
+    pub attribute_placement: AttributePlacement,
+    /// How many spaces to indent attributes by, relative to their tag
+    pub attribute_indent: usize,
+    /// How many spaces to indent children by, relative to their parent
+    pub child_indent: usize,
+    /// The width lines should fit within when laying out the output more compactly
+    pub max_width: usize,
+    /// Where to separate sibling elements with a blank line
+    pub blank_line_between_siblings: BlankLines,
+    /// Put the attributes of tags without children on the same line as the tag, when it fits within `max_width`
+    pub collapse_short_tags: bool,
+    /// Put tags with no attributes and only text on one line, e.g. `title { "Tutorial" }`, when it fits within
+    /// `max_width`
+    pub fold_text_leaves: bool,
+    /// Put chains of inline tags with no attributes, each holding only the next, on one line when they end in text, e.g.
+    /// `strong { em { "text" } }`, when it fits within `max_width`
+    pub fold_inline_chains: bool,
+    /// How to write the braces of elements with neither attributes nor children
+    pub empty_brace_style: EmptyBraces,
+    /// Write attribute values with several quotes or backslashes as raw strings, e.g. `r#"C:\"quoted\""#`
+    pub raw_strings: bool,
+    /// Trim whitespace from around attribute values, so `class=" x "` becomes `class: "x"`
+    pub trim_attribute_values: bool,
+    /// Emit values of numeric attributes, like `colspan` or `min`, which are plain numbers as number literals, e.g.
+    /// `min="0"` becomes `min: 0`
+    pub unquote_numbers: bool,
+    /// Wrap the output in a `Fragment` when the html has more than one top level node, including bare text, so the
+    /// output is a single expression
+    pub wrap_roots: bool,
+    /// Emit attribute values starting with this prefix as rust expressions rather than strings, e.g. with `rsx:`,
+    /// `value="rsx:count()"` becomes `value: count()`
+    pub expression_prefix: Option<String>,
+    /// Write expressions which are a variable named after their attribute in shorthand, e.g. with `rsx:` as the
+    /// `expression_prefix`, `value="rsx:value"` becomes `value`
+    pub shorthand_attributes: bool,
+    /// Qualify components with this module path in Dioxus rsx, e.g. with `ui`, `<Button/>` becomes `ui::Button {}`
+    pub component_module_prefix: Option<String>,
+}
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        Self {
+            ascii_only: false,
+            max_output_bytes: None,
+            max_depth: None,
+            tag_renames: HashMap::new(),
+            drop_elements: HashSet::new(),
+            tag_namespaces: TagNamespaces::default(),
+            empty_tag_names: EmptyTagNames::default(),
+            attribute_filter: Vec::new(),
+            drop_svg_xmlns: false,
+            sort_class_tokens: false,
+            dedupe_class_tokens: false,
+            template_expressions: false,
+            js_handler_style: JsHandlerStyle::default(),
+            text_braces: TextBraces::default(),
+            text_child_style: TextChildStyle::default(),
+            target: Target::default(),
+            dioxus_version: DioxusVersion::default(),
+            wrap_in_macro: false,
+            attribute_case: AttributeCase::default(),
+            unknown_attributes_as_strings: false,
+            attribute_placement: AttributePlacement::default(),
+            attribute_indent: 4,
+            child_indent: 4,
+            max_width: 100,
+            blank_line_between_siblings: BlankLines::default(),
+            collapse_short_tags: false,
+            fold_text_leaves: false,
+            fold_inline_chains: false,
+            empty_brace_style: EmptyBraces::default(),
+            raw_strings: false,
+            trim_attribute_values: false,
+            unquote_numbers: false,
+            wrap_roots: false,
+            expression_prefix: None,
+            shorthand_attributes: false,
+            component_module_prefix: None,
+        }
+    }
+}
+
+impl Hash for ConvertOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Destructured so that a new option can't be forgotten here
+        let Self {
+            ascii_only,
+            max_output_bytes,
+            max_depth,
+            tag_renames,
+            drop_elements,
+            tag_namespaces,
+            empty_tag_names,
+            attribute_filter,
+            drop_svg_xmlns,
+            sort_class_tokens,
+            dedupe_class_tokens,
+            template_expressions,
+            js_handler_style,
+            text_braces,
+            text_child_style,
+            target,
+            dioxus_version,
+            wrap_in_macro,
+            attribute_case,
+            unknown_attributes_as_strings,
+            attribute_placement,
+            attribute_indent,
+            child_indent,
+            max_width,
+            blank_line_between_siblings,
+            collapse_short_tags,
+            fold_text_leaves,
+            fold_inline_chains,
+            empty_brace_style,
+            raw_strings,
+            trim_attribute_values,
+            unquote_numbers,
+            wrap_roots,
+            expression_prefix,
+            shorthand_attributes,
+            component_module_prefix,
+        } = self;
+
+        ascii_only.hash(state);
+        max_output_bytes.hash(state);
+        max_depth.hash(state);
+        // Map iteration order isn't stable, so sort to hash equal maps equally
+        tag_renames.iter().collect::<BTreeMap<_, _>>().hash(state);
+        drop_elements.iter().collect::<BTreeSet<_>>().hash(state);
+        tag_namespaces.hash(state);
+        empty_tag_names.hash(state);
+        attribute_filter.hash(state);
+        drop_svg_xmlns.hash(state);
+        sort_class_tokens.hash(state);
+        dedupe_class_tokens.hash(state);
+        template_expressions.hash(state);
+        js_handler_style.hash(state);
+        text_braces.hash(state);
+        text_child_style.hash(state);
+        target.hash(state);
+        dioxus_version.hash(state);
+        wrap_in_macro.hash(state);
+        attribute_case.hash(state);
+        unknown_attributes_as_strings.hash(state);
+        attribute_placement.hash(state);
+        attribute_indent.hash(state);
+        child_indent.hash(state);
+        max_width.hash(state);
+        blank_line_between_siblings.hash(state);
+        collapse_short_tags.hash(state);
+        fold_text_leaves.hash(state);
+        fold_inline_chains.hash(state);
+        empty_brace_style.hash(state);
+        raw_strings.hash(state);
+        trim_attribute_values.hash(state);
+        unquote_numbers.hash(state);
+        wrap_roots.hash(state);
+        expression_prefix.hash(state);
+        shorthand_attributes.hash(state);
+        component_module_prefix.hash(state);
+    }
+}
+
+/// Named bundles of options for common setups, which expand into [`ConvertOptions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// Dioxus rsx laid out tightly, with two space indents and short tags on one line
+    Compact,
+    /// Leptos `view!` rather than Dioxus rsx
+    Leptos,
+}
+
+impl From<Preset> for ConvertOptions {
+    fn from(preset: Preset) -> Self {
+        match preset {
+            Preset::Compact => Self {
+                attribute_indent: 2,
+                child_indent: 2,
+                collapse_short_tags: true,
+                ..Self::default()
+            },
+            Preset::Leptos => Self {
+                target: Target::Leptos,
+                ..Self::default()
+            },
+        }
+    }
+}
+
+/// Frameworks that can be converted to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// Dioxus `rsx!`, e.g. `div { class: "x" }`
+    #[default]
+    Dioxus,
+    /// Leptos `view!`, e.g. `<div class="x"/>`
+    Leptos,
+}
+
+/// Where attributes go relative to the children of a tag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AttributePlacement {
+    /// Attributes first, then children
+    #[default]
+    Before,
+    /// Children first, then attributes
+    After,
+}
+
+/// Cases attribute names can be written in as rsx keys
+///
+/// Whatever the case, names html treats case-insensitively, like `CLASS` or `onClick`, are lowercased first, and
+/// hyphens are never kept, as keys must be identifiers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AttributeCase {
+    /// Split camel case humps with underscores, e.g. `viewBox` becomes `view_box`
+    #[default]
+    SnakeCase,
+    /// Keep the name as written, e.g. `dataValue` stays `dataValue`
+    Preserve,
+    /// Join hyphenated words with camel case humps, e.g. `stroke-width` becomes `strokeWidth`
+    CamelCase,
+}
+
+/// Versions of Dioxus with differing component signatures
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DioxusVersion {
+    /// Components take a scope, e.g. `fn App(cx: Scope) -> Element`, rendering with `cx.render(rsx! { ... })`
+    V0_4,
+    /// Components take no scope, e.g. `fn App() -> Element`, returning `rsx! { ... }`
+    #[default]
+    V0_5,
+}
+
+/// Ways of handling tag names with a namespace prefix, which isn't valid in an identifier
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TagNamespaces {
+    /// Drop the prefix, so `svg:rect` becomes `rect`
+    #[default]
+    Strip,
+    /// Keep the name as written, for mapping with `tag_renames`
+    Keep,
+}
+
+/// Ways of handling tags without a name, as there is nothing to emit for them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EmptyTagNames {
+    /// Fail the conversion with [`Error::EmptyTagName`](crate::Error::EmptyTagName), pointing at the tag, for
+    /// strict conversion of html that should be well formed
+    Error,
+    /// Drop the tag, emitting its children in its place, as browsers do
+    #[default]
+    Unwrap,
+}
+
+/// Ways of writing event handlers, as their javascript can't be run by rust
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum JsHandlerStyle {
+    /// A closure with the javascript kept in a comment, e.g. `move |_| { /* go() */ }`, for porting by hand
+    #[default]
+    Commented,
+    /// An empty closure, e.g. `move |_| {}`, for when the javascript isn't worth keeping
+    Empty,
+}
+
+/// Ways of handling braces in text, as strings in rsx are format strings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextBraces {
+    /// Emit the text as written, with a comment warning that rsx will treat the braces as interpolation
+    #[default]
+    Flag,
+    /// Double up the braces, so rsx shows them as written
+    Escape,
+}
+
+/// Where to put blank lines between an element and the sibling element following it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BlankLines {
+    /// Nowhere, with each element starting on the line after the last
+    #[default]
+    Never,
+    /// Between the top level elements of the html only
+    TopLevel,
+    /// Between sibling elements at every level
+    Everywhere,
+}
+
+/// Ways of writing the braces of an empty element
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EmptyBraces {
+    /// `div {}`
+    #[default]
+    Tight,
+    /// `div { }`
+    Spaced,
+}
+
+/// Ways of writing text children in Dioxus rsx
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextChildStyle {
+    /// A string literal, e.g. `"text"`, which rsx reads as a format string
+    #[default]
+    Bare,
+    /// A string expression, e.g. `{"text"}`, which is shown as written, braces and all
+    Braced,
+}
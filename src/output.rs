@@ -0,0 +1,24 @@
+use std::ops::Range;
+
+use crate::options::ConvertOptions;
+
+/// Converted rsx, alongside how it was produced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertResult {
+    /// The rsx
+    pub output: String,
+    /// The options used, after any [`Preset`](crate::Preset) was expanded
+    pub options: ConvertOptions,
+    /// Attribute names which were written differently in the rsx, alongside what they were written as, e.g.
+    /// `viewBox` as `view_box`
+    pub renamed_attributes: Vec<(String, String)>,
+}
+
+/// Where an element in the html ended up in the rsx
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceMapEntry {
+    /// The bytes of the html the element was written as, from its opening tag to its closing tag
+    pub source: Range<usize>,
+    /// The lines of the rsx the element became, counting from zero, from its name to its closing brace
+    pub output_lines: Range<usize>,
+}
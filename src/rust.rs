@@ -0,0 +1,69 @@
+/// Rust keywords, strict and reserved, which can't be used as plain identifiers
+const KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Keywords which can't be written as raw identifiers either
+const NON_RAW_KEYWORDS: &[&str] = &["Self", "crate", "self", "super"];
+
+/// Whether a name can be written as-is as a rust identifier
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first.is_alphabetic())
+        && chars.all(|chara| chara == '_' || chara.is_alphanumeric())
+}
+
+/// Whether a value can be written as-is as a rust number literal, e.g. `10` or `-0.5`
+///
+/// Integers must fit in an `i64` and decimals in an `f64`, so the literal doesn't overflow. Values with leading zeros,
+/// like `007`, or trailing fractional zeros, like `1.50`, would lose them as a literal, so aren't numbers here.
+pub fn is_number(value: &str) -> bool {
+    let unsigned = value.strip_prefix('-').unwrap_or(value);
+    let is_digits = |digits: &str| !digits.is_empty() && digits.chars().all(|x| x.is_ascii_digit());
+    let is_whole = |digits: &str| is_digits(digits) && (digits == "0" || !digits.starts_with('0'));
+
+    unsigned.split_once('.').map_or_else(
+        || is_whole(unsigned) && value.parse::<i64>().is_ok(),
+        |(whole, fraction)| {
+            is_whole(whole)
+                && is_digits(fraction)
+                && !fraction.ends_with('0')
+                && value.parse::<f64>().is_ok_and(f64::is_finite)
+        },
+    )
+}
+
+/// Whether a name is a rust keyword, so needs writing as a raw identifier, e.g. `r#type`
+pub fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// Whether a name can be used as written to declare an item, like a `fn` or `const`
+///
+/// Keywords are only accepted written as raw identifiers, e.g. `r#type`.
+pub fn is_item_name(name: &str) -> bool {
+    name.strip_prefix("r#").map_or_else(
+        || identifier(name).is_some_and(|x| x == name),
+        |raw| identifier(raw).is_some(),
+    )
+}
+
+/// The name as an identifier, written as a raw identifier if it's a keyword, e.g. `r#type`
+///
+/// Returns `None` for names that can't be an identifier at all, like `xlink:href` or `self`.
+pub fn identifier(name: &str) -> Option<String> {
+    if !is_identifier(name) || NON_RAW_KEYWORDS.contains(&name) {
+        None
+    } else if is_keyword(name) {
+        Some(format!("r#{name}"))
+    } else {
+        Some(name.to_string())
+    }
+}